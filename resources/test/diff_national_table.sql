@@ -0,0 +1,8 @@
+CREATE TABLE sales.orders (
+    id INT,
+    amount NUMERIC(10, 3),
+    code NVARCHAR(2),
+    seen DATETIME,
+    status NVARCHAR(MAX),
+    note NVARCHAR(50)
+);
@@ -0,0 +1,11 @@
+CREATE TABLE sales.orders (
+    [id] TINYINT NOT NULL,
+    [odd]](x] INT,
+    "say ""hi"", (x" INT,
+    amount NUMERIC(4, 2),
+    code CHAR(2),
+    seen DATE,
+    status VARCHAR(20),
+    region NVARCHAR(20),
+    CONSTRAINT pk_orders PRIMARY KEY ([id])
+);
@@ -0,0 +1,148 @@
+// Copyright 2023 Jonathan Bowman
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use anyhow::{bail, Context, Result};
+use std::fmt::Write;
+use std::fs;
+use std::iter::zip;
+use std::path::Path;
+
+/// Longest field bcp reads into a column of limited length
+const MAX_LENGTH: usize = 8000;
+
+/// The format file version, that of SQL Server 2017
+const VERSION: &str = "14.0";
+
+/// The bcp field length for the longest value written to a column
+fn field_length(length: usize) -> usize {
+    if length > MAX_LENGTH {
+        0
+    } else {
+        length.max(1)
+    }
+}
+
+/// Text escaped for an XML attribute
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\t' | '\n' | '\r' => {
+                let _ = write!(escaped, "&#x{:X};", u32::from(c));
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Whether XML 1.0 can hold the separator
+fn xml_safe(sep: &[u8]) -> bool {
+    sep.iter()
+        .all(|&c| !c.is_ascii_control() || matches!(c, b'\t' | b'\n' | b'\r'))
+}
+
+/// A non-XML format file reading every field as text up to its separator
+pub fn format_file(
+    columns: &[String],
+    lengths: &[usize],
+    field_sep: &[u8],
+    row_sep: &[u8],
+) -> String {
+    let (field_sep, row_sep) = (
+        String::from_utf8_lossy(field_sep),
+        String::from_utf8_lossy(row_sep),
+    );
+    let mut format = format!("{}\n{}\n", VERSION, columns.len());
+    for (i, (column, &length)) in zip(columns, lengths).enumerate() {
+        let terminator = if i + 1 == columns.len() {
+            &row_sep
+        } else {
+            &field_sep
+        };
+        let _ = writeln!(
+            format,
+            "{0}\tSQLCHAR\t0\t{1}\t\"{2}\"\t{0}\t{3}\t\"\"",
+            i + 1,
+            field_length(length),
+            terminator,
+            column
+        );
+    }
+    format
+}
+
+/// An XML format file with the same fields as [`format_file`]
+pub fn xml_format_file(
+    columns: &[String],
+    lengths: &[usize],
+    field_sep: &[u8],
+    row_sep: &[u8],
+) -> Result<String> {
+    if !xml_safe(field_sep) || !xml_safe(row_sep) {
+        bail!("XML format files cannot hold these separators; use the non-XML format file");
+    }
+    let (field_sep, row_sep) = (
+        xml_escape(&String::from_utf8_lossy(field_sep)),
+        xml_escape(&String::from_utf8_lossy(row_sep)),
+    );
+    let mut record = String::new();
+    let mut row = String::new();
+    for (i, (column, &length)) in zip(columns, lengths).enumerate() {
+        let terminator = if i + 1 == columns.len() {
+            &row_sep
+        } else {
+            &field_sep
+        };
+        let max_length = match field_length(length) {
+            0 => String::new(),
+            length => format!(" MAX_LENGTH=\"{}\"", length),
+        };
+        let _ = writeln!(
+            record,
+            "  <FIELD ID=\"{}\" xsi:type=\"CharTerm\" TERMINATOR=\"{}\"{}/>",
+            i + 1,
+            terminator,
+            max_length
+        );
+        let _ = writeln!(
+            row,
+            "  <COLUMN SOURCE=\"{}\" NAME=\"{}\"/>",
+            i + 1,
+            xml_escape(column)
+        );
+    }
+    Ok(format!(
+        "<?xml version=\"1.0\"?>\n\
+        <BCPFORMAT xmlns=\"http://schemas.microsoft.com/sqlserver/2004/bulkload/format\" \
+        xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\">\n \
+        <RECORD>\n{} </RECORD>\n <ROW>\n{} </ROW>\n</BCPFORMAT>\n",
+        record, row
+    ))
+}
+
+/// Write the `.fmt` format file, and the `.xml` one if it can hold the separators
+pub fn format_files(
+    path: &Path,
+    columns: &[String],
+    lengths: &[usize],
+    field_sep: &[u8],
+    row_sep: &[u8],
+) -> Result<()> {
+    let fmt_path = path.with_extension("fmt");
+    fs::write(&fmt_path, format_file(columns, lengths, field_sep, row_sep))
+        .with_context(|| format!("Failed to write format file {:?}", fmt_path))?;
+    let Ok(xml) = xml_format_file(columns, lengths, field_sep, row_sep) else {
+        return Ok(());
+    };
+    let xml_path = path.with_extension("xml");
+    fs::write(&xml_path, xml).with_context(|| format!("Failed to write format file {:?}", xml_path))
+}
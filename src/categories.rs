@@ -0,0 +1,193 @@
+// Copyright 2023 Jonathan Bowman
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::dialect::Dialect;
+use crate::infer::{SQLType, SQLTypeName};
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+/// Default most distinct values for a column to count as a category
+pub const DEFAULT_LIMIT: usize = 20;
+
+/// How the allowed values of a category column are enforced
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CategoryStyle {
+    /// a `CHECK (column IN (...))` constraint on the table
+    Check,
+    /// a separate lookup table referenced by a foreign key
+    Lookup,
+}
+
+impl FromStr for CategoryStyle {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &s.to_ascii_lowercase()[..] {
+            "check" => Ok(CategoryStyle::Check),
+            "lookup" => Ok(CategoryStyle::Lookup),
+            _ => bail!("Unknown category style {:?}, expected check or lookup", s),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Categories {
+    pub style: CategoryStyle,
+    pub limit: usize,
+}
+
+/// Distinct values of a column, abandoned once there are more than the limit
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Distinct {
+    values: Option<BTreeSet<String>>,
+    limit: usize,
+    count: usize,
+}
+
+impl Distinct {
+    pub fn new(limit: usize) -> Self {
+        Distinct {
+            values: Some(BTreeSet::new()),
+            limit,
+            count: 0,
+        }
+    }
+
+    pub fn update(&mut self, value: &[u8]) {
+        if value.is_empty() {
+            return;
+        }
+        self.count += 1;
+        if let Some(values) = &mut self.values {
+            let value = String::from_utf8_lossy(value);
+            if !values.contains(&*value) {
+                if values.len() == self.limit {
+                    self.values = None;
+                } else {
+                    values.insert(value.into_owned());
+                }
+            }
+        }
+    }
+
+    /// Take in the values another part of the column had
+    pub fn merge(&mut self, other: &Distinct) {
+        self.limit = self.limit.min(other.limit);
+        self.count += other.count;
+        self.values = match (self.values.take(), &other.values) {
+            (Some(mut values), Some(others)) => {
+                values.extend(others.iter().cloned());
+                (values.len() <= self.limit).then_some(values)
+            }
+            _ => None,
+        };
+    }
+
+    /// The allowed values, if this looks like a category column
+    pub fn categories(&self, sqltype: &SQLType) -> Option<Vec<String>> {
+        let values = self.values.as_ref()?;
+        if values.is_empty()
+            || sqltype.duration
+            || values.len() * 2 > self.count
+            || !matches!(
+                sqltype.name,
+                SQLTypeName::Tinyint
+                    | SQLTypeName::Smallint
+                    | SQLTypeName::Int
+                    | SQLTypeName::Bigint
+                    | SQLTypeName::Char
+                    | SQLTypeName::Varchar
+            )
+        {
+            return None;
+        }
+        Some(values.iter().cloned().collect())
+    }
+}
+
+/// A value as a SQL literal of the column's type
+pub fn literal(value: &str, sqltype: &SQLType) -> String {
+    match sqltype.name {
+        SQLTypeName::Char | SQLTypeName::Varchar => format!("'{}'", value.replace('\'', "''")),
+        _ => value.trim().to_string(),
+    }
+}
+
+fn literals(values: &[String], sqltype: &SQLType) -> String {
+    values
+        .iter()
+        .map(|value| literal(value, sqltype))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub fn check_constraint(column: &str, values: &[String], sqltype: &SQLType) -> String {
+    format!("CHECK ({} IN ({}))", column, literals(values, sqltype))
+}
+
+/// Name of the lookup table holding the values of a category column
+pub fn lookup_name(tablename: &str, column: &str) -> String {
+    format!("{}_{}", tablename, column)
+}
+
+/// Definition of the one column of a lookup table
+pub fn lookup_definition(column: &str, type_name: &str) -> String {
+    format!("{} {} NOT NULL PRIMARY KEY", column, type_name)
+}
+
+/// Statements inserting the values missing from a lookup table
+pub fn lookup_rows(
+    lookup: &str,
+    column: &str,
+    values: &[String],
+    sqltype: &SQLType,
+    dialect: Dialect,
+    kept: bool,
+) -> String {
+    if !kept && !matches!(dialect, Dialect::Oracle { .. }) {
+        let rows = values
+            .iter()
+            .map(|value| format!("({})", literal(value, sqltype)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        return format!("INSERT INTO {} ({}) VALUES {};\n", lookup, column, rows);
+    }
+    if !kept {
+        return values
+            .iter()
+            .map(|value| {
+                format!(
+                    "INSERT INTO {} ({}) VALUES ({});\n",
+                    lookup,
+                    column,
+                    literal(value, sqltype)
+                )
+            })
+            .collect();
+    }
+    let from = match dialect {
+        Dialect::Mysql | Dialect::Oracle { .. } => " FROM DUAL",
+        _ => "",
+    };
+    values
+        .iter()
+        .map(|value| {
+            let value = literal(value, sqltype);
+            format!(
+                "INSERT INTO {0} ({1}) SELECT {2}{3} \
+                WHERE NOT EXISTS (SELECT 1 FROM {0} WHERE {1} = {2});\n",
+                lookup, column, value, from
+            )
+        })
+        .collect()
+}
+
+pub fn foreign_key(lookup: &str, column: &str) -> String {
+    format!("FOREIGN KEY ({0}) REFERENCES {1} ({0})", column, lookup)
+}
@@ -24,6 +24,14 @@ enum Subcommands {
     Schema(SchemaCmd),
     View(ViewCmd),
     Stats(StatsCmd),
+    Keys(KeysCmd),
+    Relations(RelationsCmd),
+    Padding(PaddingCmd),
+    Save(SaveCmd),
+    Merge(MergeCmd),
+    Create(CreateCmd),
+    Load(LoadCmd),
+    Diff(DiffCmd),
 }
 
 /// Output special formats from CSV input
@@ -53,6 +61,54 @@ struct OutputCmd {
     /// rows per page (0 for no paging)
     #[argh(option, short = 'p', default = "0")]
     pagesize: usize,
+
+    /// detect Unix epoch timestamps in integer columns
+    #[argh(switch, short = 'e')]
+    epoch: bool,
+
+    /// keep the raw integer alongside each epoch column
+    #[argh(switch)]
+    keep_epoch: bool,
+
+    /// TOML or JSON file of column type overrides
+    #[argh(option)]
+    overrides: Option<PathBuf>,
+
+    /// type for numbers too large to hold exactly: widen, float or varchar
+    #[argh(option, default = "Default::default()")]
+    out_of_range: busser::infer::limits::RangePolicy,
+
+    /// read numbers with leading zeros as text or number
+    #[argh(option, default = "Default::default()")]
+    leading_zeros: busser::zeros::ZeroPolicy,
+
+    /// store durations as seconds, or as time when under 24 hours
+    #[argh(option, default = "Default::default()")]
+    durations: busser::infer::duration::DurationPolicy,
+
+    /// database to write for: sqlserver, postgres, mysql, oracle or oracle11
+    #[argh(option, default = "Default::default()")]
+    dialect: busser::dialect::Dialect,
+
+    /// write columns named with reserved words quoted, or with a prefix or suffix
+    #[argh(option, default = "Default::default()")]
+    identifiers: busser::dialect::IdentifierPolicy,
+
+    /// make the table with replace, if-not-exists, staging, truncate or temp
+    #[argh(option, default = "Default::default()")]
+    create: busser::create::CreateStrategy,
+
+    /// schema of a table named without one
+    #[argh(option)]
+    schema: Option<String>,
+
+    /// describe each column by its raw header in the table's metadata
+    #[argh(switch)]
+    describe: bool,
+
+    /// TOML or JSON file of column descriptions, merged with the raw headers
+    #[argh(option)]
+    descriptions: Option<PathBuf>,
 }
 
 /// Show CSV columns
@@ -70,6 +126,14 @@ struct ColumnsCmd {
     #[argh(switch, short = 'r')]
     /// get raw columns verbatim from CSV file
     raw: bool,
+
+    /// database to name columns for: sqlserver, postgres, sqlite, mysql, oracle, oracle11
+    #[argh(option, default = "Default::default()")]
+    dialect: busser::dialect::Dialect,
+
+    /// write columns named with reserved words quoted, or with a prefix or suffix
+    #[argh(option, default = "Default::default()")]
+    identifiers: busser::dialect::IdentifierPolicy,
 }
 
 /// Get stats on CSV file
@@ -87,6 +151,222 @@ struct StatsCmd {
     /// compute UTF-8 character lengths
     #[argh(switch, short = 'u')]
     utf8: bool,
+
+    /// TOML or JSON file of column type overrides
+    #[argh(option)]
+    overrides: Option<PathBuf>,
+
+    /// collect distinct values of columns with at most this many
+    #[argh(option)]
+    category_limit: Option<usize>,
+}
+
+/// Find columns that could serve as keys
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "keys")]
+struct KeysCmd {
+    /// CSV file path
+    #[argh(positional)]
+    csvfile: PathBuf,
+
+    /// most columns in a composite key
+    #[argh(option, short = 'm', default = "2")]
+    max_columns: usize,
+
+    /// memory for exact value sets, in MiB, before switching to hashes
+    #[argh(option, default = "256")]
+    memory: usize,
+}
+
+/// Suggest foreign keys and a load order across related CSV files
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "relations")]
+struct RelationsCmd {
+    /// CSV files, or directories of them
+    #[argh(positional)]
+    paths: Vec<PathBuf>,
+
+    /// memory for the key values of all files, in MiB
+    #[argh(option, default = "256")]
+    memory: usize,
+}
+
+/// Find columns of digits zero-padded to one width
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "padding")]
+struct PaddingCmd {
+    /// CSV file path
+    #[argh(positional)]
+    csvfile: PathBuf,
+}
+
+/// Save inferred column types and stats to a JSON state file
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "save")]
+struct SaveCmd {
+    /// CSV file path
+    #[argh(positional)]
+    csvfile: PathBuf,
+
+    /// state file to write
+    #[argh(option, short = 'o')]
+    output: PathBuf,
+
+    /// TOML or JSON file of column type overrides
+    #[argh(option)]
+    overrides: Option<PathBuf>,
+
+    /// type for numbers too large to hold exactly: widen, float or varchar
+    #[argh(option, default = "Default::default()")]
+    out_of_range: busser::infer::limits::RangePolicy,
+
+    /// read numbers with leading zeros as text or number
+    #[argh(option, default = "Default::default()")]
+    leading_zeros: busser::zeros::ZeroPolicy,
+
+    /// store durations as seconds, or as time when under 24 hours
+    #[argh(option, default = "Default::default()")]
+    durations: busser::infer::duration::DurationPolicy,
+}
+
+/// Merge state files saved from files of the same table
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "merge")]
+struct MergeCmd {
+    /// state files
+    #[argh(positional)]
+    statefiles: Vec<PathBuf>,
+
+    /// state file to write
+    #[argh(option, short = 'o')]
+    output: PathBuf,
+}
+
+/// Get SQL table schema from one or more state files
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "create")]
+struct CreateCmd {
+    /// state files
+    #[argh(positional)]
+    statefiles: Vec<PathBuf>,
+
+    /// SQL table name
+    #[argh(option, short = 't')]
+    table: String,
+
+    /// database to write for: sqlserver, postgres, sqlite, mysql, oracle or oracle11
+    #[argh(option, default = "Default::default()")]
+    dialect: busser::dialect::Dialect,
+
+    /// make the table with replace, if-not-exists, staging, truncate or temp
+    #[argh(option, default = "Default::default()")]
+    create: busser::create::CreateStrategy,
+
+    /// schema of a table named without one
+    #[argh(option)]
+    schema: Option<String>,
+
+    /// describe each column by its raw header in the table's metadata
+    #[argh(switch)]
+    describe: bool,
+
+    /// TOML or JSON file of column descriptions, merged with the raw headers
+    #[argh(option)]
+    descriptions: Option<PathBuf>,
+}
+
+/// Load a CSV file into a table of a SQLite database
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "load")]
+struct LoadCmd {
+    /// CSV file path
+    #[argh(positional)]
+    csvfile: PathBuf,
+
+    /// SQLite database file, created if it does not exist
+    #[argh(option)]
+    sqlite: PathBuf,
+
+    /// SQL table name, by default the name of the CSV file
+    #[argh(option, short = 't')]
+    table: Option<String>,
+
+    /// rows inserted per transaction
+    #[argh(option, default = "busser::sqlite::DEFAULT_BATCH")]
+    batch: usize,
+
+    /// detect Unix epoch timestamps in integer columns
+    #[argh(switch, short = 'e')]
+    epoch: bool,
+
+    /// TOML or JSON file of column type overrides
+    #[argh(option)]
+    overrides: Option<PathBuf>,
+
+    /// type for numbers too large to hold exactly: widen, float or varchar
+    #[argh(option, default = "Default::default()")]
+    out_of_range: busser::infer::limits::RangePolicy,
+
+    /// read numbers with leading zeros as text or number
+    #[argh(option, default = "Default::default()")]
+    leading_zeros: busser::zeros::ZeroPolicy,
+
+    /// store durations as seconds, or as time when under 24 hours
+    #[argh(option, default = "Default::default()")]
+    durations: busser::infer::duration::DurationPolicy,
+
+    /// write columns named with reserved words quoted, or with a prefix or suffix
+    #[argh(option, default = "Default::default()")]
+    identifiers: busser::dialect::IdentifierPolicy,
+
+    /// make the table with replace, if-not-exists, staging, truncate or temp
+    #[argh(option, default = "Default::default()")]
+    create: busser::create::CreateStrategy,
+}
+
+/// Get the ALTER TABLE statements bringing an existing table up to a CSV file
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "diff")]
+struct DiffCmd {
+    /// CSV file path
+    #[argh(positional)]
+    csvfile: PathBuf,
+
+    /// CREATE TABLE statement or JSON state file of the existing table
+    #[argh(positional)]
+    definition: PathBuf,
+
+    /// SQL table name, by default the one in the CREATE TABLE statement
+    #[argh(option, short = 't')]
+    table: Option<String>,
+
+    /// detect Unix epoch timestamps in integer columns
+    #[argh(switch, short = 'e')]
+    epoch: bool,
+
+    /// TOML or JSON file of column type overrides
+    #[argh(option)]
+    overrides: Option<PathBuf>,
+
+    /// type for numbers too large to hold exactly: widen, float or varchar
+    #[argh(option, default = "Default::default()")]
+    out_of_range: busser::infer::limits::RangePolicy,
+
+    /// read numbers with leading zeros as text or number
+    #[argh(option, default = "Default::default()")]
+    leading_zeros: busser::zeros::ZeroPolicy,
+
+    /// store durations as seconds, or as time when under 24 hours
+    #[argh(option, default = "Default::default()")]
+    durations: busser::infer::duration::DurationPolicy,
+
+    /// database the table is in; only sqlserver is compared so far
+    #[argh(option, default = "Default::default()")]
+    dialect: busser::dialect::Dialect,
+
+    /// write columns named with reserved words quoted, or with a prefix or suffix
+    #[argh(option, default = "Default::default()")]
+    identifiers: busser::dialect::IdentifierPolicy,
 }
 
 /// View CSV file
@@ -133,15 +413,221 @@ struct SchemaCmd {
     /// use only varchars as type
     #[argh(switch, short = 'c')]
     chars: bool,
+
+    /// detect Unix epoch timestamps in integer columns
+    #[argh(switch, short = 'e')]
+    epoch: bool,
+
+    /// keep the raw integer alongside each epoch column
+    #[argh(switch)]
+    keep_epoch: bool,
+
+    /// TOML or JSON file of column type overrides
+    #[argh(option)]
+    overrides: Option<PathBuf>,
+
+    /// show which values determined each column type
+    #[argh(switch, short = 'x')]
+    explain: bool,
+
+    /// add PRIMARY KEY and UNIQUE constraints found in the data
+    #[argh(switch, short = 'k')]
+    keys: bool,
+
+    /// enforce columns with few distinct values: check or lookup
+    #[argh(option)]
+    categories: Option<busser::categories::CategoryStyle>,
+
+    /// most distinct values for a column to count as a category
+    #[argh(option, default = "busser::categories::DEFAULT_LIMIT")]
+    category_limit: usize,
+
+    /// type for numbers too large to hold exactly: widen, float or varchar
+    #[argh(option, default = "Default::default()")]
+    out_of_range: busser::infer::limits::RangePolicy,
+
+    /// read numbers with leading zeros as text or number
+    #[argh(option, default = "Default::default()")]
+    leading_zeros: busser::zeros::ZeroPolicy,
+
+    /// store durations as seconds, or as time when under 24 hours
+    #[argh(option, default = "Default::default()")]
+    durations: busser::infer::duration::DurationPolicy,
+
+    /// database to write for: sqlserver, postgres, sqlite, mysql, oracle or oracle11
+    #[argh(option, default = "Default::default()")]
+    dialect: busser::dialect::Dialect,
+
+    /// write columns named with reserved words quoted, or with a prefix or suffix
+    #[argh(option, default = "Default::default()")]
+    identifiers: busser::dialect::IdentifierPolicy,
+
+    /// make the table with replace, if-not-exists, staging, truncate or temp
+    #[argh(option, default = "Default::default()")]
+    create: busser::create::CreateStrategy,
+
+    /// schema of a table named without one
+    #[argh(option)]
+    schema: Option<String>,
+
+    /// add a geography point for each latitude and longitude column pair
+    #[argh(switch)]
+    points: bool,
+
+    /// describe each column by its raw header in the table's metadata
+    #[argh(switch)]
+    describe: bool,
+
+    /// TOML or JSON file of column descriptions, merged with the raw headers
+    #[argh(option)]
+    descriptions: Option<PathBuf>,
+}
+
+fn load_descriptions(
+    describe: bool,
+    path: Option<PathBuf>,
+) -> Result<Option<busser::descriptions::Descriptions>> {
+    match path {
+        Some(path) => busser::descriptions::Descriptions::load(path).map(Some),
+        None => Ok(describe.then(Default::default)),
+    }
+}
+
+fn load_overrides(path: Option<PathBuf>) -> Result<busser::overrides::Overrides> {
+    if let Some(path) = path {
+        busser::overrides::Overrides::load(path)
+    } else {
+        Ok(Default::default())
+    }
 }
 
 fn stats(args: StatsCmd) -> Result<()> {
-    let stats = busser::csv_survey(&args.csvfile, args.infer, args.utf8, None, None, None)?;
+    let options = busser::SchemaOptions {
+        overrides: load_overrides(args.overrides)?,
+        categories: args
+            .category_limit
+            .map(|limit| busser::categories::Categories {
+                style: busser::categories::CategoryStyle::Check,
+                limit,
+            }),
+        ..Default::default()
+    };
+    let stats = busser::csv_survey(
+        &args.csvfile,
+        args.infer,
+        args.utf8,
+        None,
+        None,
+        None,
+        &options,
+    )?;
     dbg!(stats);
     //println!("{:?}", stats);
     Ok(())
 }
 
+fn keys(args: KeysCmd) -> Result<()> {
+    let report = busser::csv_keys(&args.csvfile, args.max_columns, args.memory * 1024 * 1024)?;
+    print!("{}", report);
+    Ok(())
+}
+
+fn relations(args: RelationsCmd) -> Result<()> {
+    let report = busser::csv_relations(&args.paths, args.memory * 1024 * 1024)?;
+    print!("{}", report);
+    Ok(())
+}
+
+fn padding(args: PaddingCmd) -> Result<()> {
+    let report = busser::csv_padding(&args.csvfile)?;
+    print!("{}", report);
+    Ok(())
+}
+
+fn save(args: SaveCmd) -> Result<()> {
+    let options = busser::SchemaOptions {
+        overrides: load_overrides(args.overrides)?,
+        out_of_range: args.out_of_range,
+        leading_zeros: args.leading_zeros,
+        durations: args.durations,
+        ..Default::default()
+    };
+    let stats = busser::csv_survey(&args.csvfile, true, false, None, None, None, &options)?;
+    stats.save(&args.output)
+}
+
+fn load_states(statefiles: &[PathBuf]) -> Result<busser::CsvStats> {
+    let Some((first, rest)) = statefiles.split_first() else {
+        anyhow::bail!("No state files given");
+    };
+    let mut stats = busser::CsvStats::load(first)?;
+    for statefile in rest {
+        stats.merge(&busser::CsvStats::load(statefile)?)?;
+    }
+    Ok(stats)
+}
+
+fn merge(args: MergeCmd) -> Result<()> {
+    load_states(&args.statefiles)?.save(&args.output)
+}
+
+fn create(args: CreateCmd) -> Result<()> {
+    let options = busser::SchemaOptions {
+        dialect: args.dialect,
+        create: args.create,
+        schema: args.schema,
+        descriptions: load_descriptions(args.describe, args.descriptions)?,
+        ..Default::default()
+    };
+    let create_table = load_states(&args.statefiles)?.schema(&args.table, &options)?;
+    println!("{}", create_table);
+    Ok(())
+}
+
+fn load(args: LoadCmd) -> Result<()> {
+    let options = busser::SchemaOptions {
+        epoch: args.epoch,
+        overrides: load_overrides(args.overrides)?,
+        out_of_range: args.out_of_range,
+        leading_zeros: args.leading_zeros,
+        durations: args.durations,
+        dialect: busser::dialect::Dialect::Sqlite,
+        identifiers: args.identifiers,
+        create: args.create,
+        ..Default::default()
+    };
+    let (rows, table) = busser::sqlite::load(
+        &args.csvfile,
+        &args.sqlite,
+        args.table.as_deref(),
+        args.batch,
+        &options,
+    )?;
+    println!("Loaded {} rows into {} in {:?}", rows, table, args.sqlite);
+    Ok(())
+}
+
+fn diff(args: DiffCmd) -> Result<()> {
+    let options = busser::SchemaOptions {
+        epoch: args.epoch,
+        overrides: load_overrides(args.overrides)?,
+        out_of_range: args.out_of_range,
+        leading_zeros: args.leading_zeros,
+        durations: args.durations,
+        dialect: args.dialect,
+        identifiers: args.identifiers,
+        ..Default::default()
+    };
+    let statements = busser::csv_diff(
+        &args.csvfile,
+        &args.definition,
+        args.table.as_deref(),
+        &options,
+    )?;
+    print!("{}", statements);
+    Ok(())
+}
+
 fn view(args: ViewCmd) -> Result<()> {
     busser::view::view(
         &args.csvfile,
@@ -156,13 +642,43 @@ fn view(args: ViewCmd) -> Result<()> {
 }
 
 fn columns(args: ColumnsCmd) -> Result<()> {
-    let columns = busser::csv_columns(&args.csvfile, args.table.as_deref(), args.raw, None, None)?;
+    let columns = busser::csv_columns(
+        &args.csvfile,
+        args.table.as_deref(),
+        args.raw,
+        None,
+        None,
+        args.dialect,
+        args.identifiers,
+    )?;
     println!("{}", columns.join(", "));
     Ok(())
 }
 
 fn schema(args: SchemaCmd) -> Result<()> {
-    let create_table = busser::csv_schema(&args.csvfile, &args.table, args.asciidelimited)?;
+    let options = busser::SchemaOptions {
+        epoch: args.epoch,
+        keep_epoch: args.keep_epoch,
+        overrides: load_overrides(args.overrides)?,
+        explain: args.explain,
+        keys: args.keys,
+        categories: args.categories.map(|style| busser::categories::Categories {
+            style,
+            limit: args.category_limit,
+        }),
+        out_of_range: args.out_of_range,
+        leading_zeros: args.leading_zeros,
+        durations: args.durations,
+        points: args.points,
+        dialect: args.dialect,
+        identifiers: args.identifiers,
+        create: args.create,
+        schema: args.schema,
+        descriptions: load_descriptions(args.describe, args.descriptions)?,
+        ..Default::default()
+    };
+    let create_table =
+        busser::csv_schema(&args.csvfile, &args.table, args.asciidelimited, &options)?;
     println!("{}", create_table);
     Ok(())
 }
@@ -174,15 +690,62 @@ fn output(args: OutputCmd) -> Result<()> {
         "txt".to_string()
     };
     let outfile = busser::determine_output_path(args.output, &args.table, &extension)?;
+    // the fast path copies values as they are, so anything shaping them by
+    // their type needs the types inferred first
+    let infer = args.infer
+        || args.epoch
+        || args.keep_epoch
+        || args.overrides.is_some()
+        || args.out_of_range != Default::default()
+        || args.leading_zeros != Default::default()
+        || args.durations != Default::default()
+        || args.describe
+        || args.descriptions.is_some();
+    let options = busser::SchemaOptions {
+        epoch: args.epoch,
+        keep_epoch: args.keep_epoch,
+        overrides: load_overrides(args.overrides)?,
+        out_of_range: args.out_of_range,
+        leading_zeros: args.leading_zeros,
+        durations: args.durations,
+        dialect: args.dialect,
+        identifiers: args.identifiers,
+        create: args.create,
+        schema: args.schema,
+        descriptions: load_descriptions(args.describe, args.descriptions)?,
+        ..Default::default()
+    };
     if args.json {
-        busser::csv_into_json(&args.csvfile, &outfile, &args.table, args.pagesize)?;
+        busser::csv_into_json(
+            &args.csvfile,
+            &outfile,
+            &args.table,
+            args.pagesize,
+            &options,
+        )?;
+    } else if infer
+        || args.dialect != busser::dialect::Dialect::SqlServer
+        || args.create != Default::default()
+    {
+        // only bcp reads the fast path's ASCII-delimited rows, other
+        // databases need each value in the form of its type, and the fast
+        // path makes no table
+        busser::csv_into_bcp(
+            &args.csvfile,
+            &outfile,
+            &args.table,
+            true,
+            args.pagesize,
+            &options,
+        )?;
     } else {
         busser::csv_into_bcp_fast(
             &args.csvfile,
             &outfile,
             &args.table,
-            args.infer,
+            infer,
             args.pagesize,
+            &options,
         )?;
     }
     Ok(())
@@ -195,6 +758,14 @@ pub fn run(args: Args) -> Result<()> {
         Subcommands::Schema(args) => schema(args)?,
         Subcommands::View(args) => view(args)?,
         Subcommands::Stats(args) => stats(args)?,
+        Subcommands::Keys(args) => keys(args)?,
+        Subcommands::Relations(args) => relations(args)?,
+        Subcommands::Padding(args) => padding(args)?,
+        Subcommands::Save(args) => save(args)?,
+        Subcommands::Merge(args) => merge(args)?,
+        Subcommands::Create(args) => create(args)?,
+        Subcommands::Load(args) => load(args)?,
+        Subcommands::Diff(args) => diff(args)?,
     }
 
     Ok(())
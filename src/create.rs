@@ -0,0 +1,155 @@
+// Copyright 2023 Jonathan Bowman
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::dialect::{Dialect, TableName};
+use anyhow::bail;
+use std::str::FromStr;
+use time::OffsetDateTime;
+
+/// How the table is made ready for the rows loaded into it
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CreateStrategy {
+    /// drop any table of the same name and create it anew
+    #[default]
+    Replace,
+    /// create the table unless it exists, keeping any rows it has
+    IfNotExists,
+    /// create a new table named for the target and the time
+    Staging,
+    /// create the table unless it exists, and empty it
+    Truncate,
+    /// create a temporary table, gone at the end of the session
+    Temp,
+}
+
+impl FromStr for CreateStrategy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &s.to_ascii_lowercase()[..] {
+            "replace" => Ok(CreateStrategy::Replace),
+            "if-not-exists" => Ok(CreateStrategy::IfNotExists),
+            "staging" => Ok(CreateStrategy::Staging),
+            "truncate" => Ok(CreateStrategy::Truncate),
+            "temp" => Ok(CreateStrategy::Temp),
+            _ => bail!(
+                "Unknown create strategy {:?}, expected replace, if-not-exists, staging, \
+                truncate or temp",
+                s
+            ),
+        }
+    }
+}
+
+/// A PL/SQL block running the statement and ignoring one Oracle error code
+fn oracle_ignoring(statement: &str, code: i32) -> String {
+    format!(
+        "BEGIN\n  EXECUTE IMMEDIATE '{}';\nEXCEPTION\n  WHEN OTHERS THEN\n    \
+        IF SQLCODE != {} THEN\n      RAISE;\n    END IF;\nEND;\n/",
+        statement.replace('\'', "''"),
+        code
+    )
+}
+
+/// The current time as digits, as in `20240131235959`
+fn timestamp() -> String {
+    let now = OffsetDateTime::now_utc();
+    format!(
+        "{:04}{:02}{:02}{:02}{:02}{:02}",
+        now.year(),
+        u8::from(now.month()),
+        now.day(),
+        now.hour(),
+        now.minute(),
+        now.second()
+    )
+}
+
+impl CreateStrategy {
+    /// The table rows are written to
+    pub fn target(self, table: &TableName, dialect: Dialect) -> TableName {
+        match self {
+            CreateStrategy::Staging => {
+                let suffix = format!("_staging_{}", timestamp());
+                let name = dialect.truncate(&table.table, suffix.len());
+                table.sibling(&format!("{}{}", name, suffix))
+            }
+            CreateStrategy::Temp => TableName {
+                database: None,
+                schema: None,
+                table: if dialect == Dialect::SqlServer {
+                    format!("#{}", table.table)
+                } else {
+                    table.table.clone()
+                },
+            },
+            _ => table.clone(),
+        }
+    }
+
+    /// The strategy for the lookup tables a table refers to
+    pub fn lookup(self) -> CreateStrategy {
+        match self {
+            CreateStrategy::Truncate => CreateStrategy::IfNotExists,
+            strategy => strategy,
+        }
+    }
+
+    /// Statements clearing the way for the table, and those creating it
+    pub fn statements(
+        self,
+        table: &TableName,
+        definitions: &str,
+        dialect: Dialect,
+    ) -> (String, String) {
+        let name = table.qualified(dialect);
+        let create = format!("CREATE TABLE {} ({});", name, definitions);
+        let if_not_exists = match dialect {
+            Dialect::SqlServer => format!(
+                "IF OBJECT_ID(N'{}', N'U') IS NULL\n{}",
+                name.replace('\'', "''"),
+                create
+            ),
+            // ORA-00955: name is already used by an existing object
+            Dialect::Oracle { .. } => {
+                oracle_ignoring(&format!("CREATE TABLE {} ({})", name, definitions), -955)
+            }
+            _ => format!("CREATE TABLE IF NOT EXISTS {} ({});", name, definitions),
+        };
+        let drop = match dialect {
+            // ORA-00942: table or view does not exist
+            Dialect::Oracle { .. } => oracle_ignoring(&format!("DROP TABLE {}", name), -942),
+            _ => format!("DROP TABLE IF EXISTS {};", name),
+        };
+        match self {
+            CreateStrategy::Replace => (format!("{}\n", drop), create),
+            CreateStrategy::IfNotExists => (String::new(), if_not_exists),
+            CreateStrategy::Staging => (String::new(), create),
+            CreateStrategy::Truncate => {
+                let empty = match dialect {
+                    Dialect::Sqlite => format!("DELETE FROM {};", name),
+                    _ => format!("TRUNCATE TABLE {};", name),
+                };
+                (String::new(), format!("{}\n{}", if_not_exists, empty))
+            }
+            CreateStrategy::Temp => {
+                let create = match dialect {
+                    Dialect::SqlServer => create,
+                    Dialect::Sqlite => format!("CREATE TEMP TABLE {} ({});", name, definitions),
+                    Dialect::Postgres | Dialect::Mysql => {
+                        format!("CREATE TEMPORARY TABLE {} ({});", name, definitions)
+                    }
+                    Dialect::Oracle { .. } => format!(
+                        "CREATE GLOBAL TEMPORARY TABLE {} ({}) ON COMMIT PRESERVE ROWS;",
+                        name, definitions
+                    ),
+                };
+                (String::new(), create)
+            }
+        }
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright 2023 Jonathan Bowman
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::dialect::{Dialect, TableName};
+use crate::infer::SQLType;
+use anyhow::{bail, Context, Result};
+use std::collections::BTreeMap;
+use std::fs;
+use std::iter::zip;
+use std::path::Path;
+
+/// Column descriptions given by the user, keyed by column name
+#[derive(Clone, Debug, Default)]
+pub struct Descriptions {
+    columns: BTreeMap<String, String>,
+}
+
+impl Descriptions {
+    /// Read descriptions from a JSON or TOML file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read descriptions from {:?}", path))?;
+        let columns = if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+        {
+            serde_json::from_str(&text)
+                .with_context(|| format!("Failed to parse descriptions in {:?}", path))?
+        } else {
+            toml::from_str(&text)
+                .with_context(|| format!("Failed to parse descriptions in {:?}", path))?
+        };
+        Ok(Descriptions { columns })
+    }
+
+    /// The description of each column, matching the raw header first
+    pub fn resolve(
+        &self,
+        raw_columns: &[String],
+        columns: &[String],
+    ) -> Result<Vec<Option<String>>> {
+        for key in self.columns.keys() {
+            if !raw_columns.contains(key) && !columns.contains(key) {
+                bail!("Description given for unknown column {:?}", key);
+            }
+        }
+        Ok(zip(raw_columns, columns)
+            .map(|(raw, column)| {
+                self.columns
+                    .get(raw)
+                    .or_else(|| self.columns.get(column))
+                    .cloned()
+                    .or_else(|| (raw != column).then(|| raw.clone()))
+            })
+            .collect())
+    }
+}
+
+/// Text as a string literal, with quotes doubled
+fn literal(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+/// The statements attaching each description to its column
+pub fn statements(
+    table: &TableName,
+    columns: &[String],
+    sqltypes: &[SQLType],
+    descriptions: &[Option<String>],
+    dialect: Dialect,
+) -> Result<String> {
+    let described = || {
+        zip(columns, zip(sqltypes, descriptions))
+            .filter_map(|(column, (sqltype, text))| Some((column, sqltype, text.as_ref()?)))
+    };
+    let name = table.qualified(dialect);
+    let mut statements = String::new();
+    match dialect {
+        Dialect::SqlServer if table.table.starts_with('#') => {}
+        Dialect::SqlServer => {
+            // the procedure of another database is called through it
+            let procedure = match &table.database {
+                Some(database) => format!(
+                    "{}.sys.sp_addextendedproperty",
+                    dialect.identifier(database)
+                ),
+                None => "sp_addextendedproperty".to_string(),
+            };
+            let schema = table.schema.as_deref().unwrap_or("dbo");
+            for (column, _, text) in described() {
+                statements.push_str(&format!(
+                    "EXEC {} @name = N'MS_Description', @value = N{}, \
+                    @level0type = N'SCHEMA', @level0name = N{}, \
+                    @level1type = N'TABLE', @level1name = N{}, \
+                    @level2type = N'COLUMN', @level2name = N{};\n",
+                    procedure,
+                    literal(text),
+                    literal(schema),
+                    literal(&table.table),
+                    literal(column)
+                ));
+            }
+        }
+        Dialect::Postgres | Dialect::Oracle { .. } => {
+            for (column, _, text) in described() {
+                statements.push_str(&format!(
+                    "COMMENT ON COLUMN {}.{} IS {};\n",
+                    name,
+                    dialect.identifier(column),
+                    literal(text)
+                ));
+            }
+        }
+        Dialect::Mysql => {
+            for (column, sqltype, text) in described() {
+                statements.push_str(&format!(
+                    "ALTER TABLE {} MODIFY COLUMN {} {}{} COMMENT {};\n",
+                    name,
+                    dialect.identifier(column),
+                    dialect.type_name(sqltype),
+                    if sqltype.not_null { " NOT NULL" } else { "" },
+                    literal(&text.replace('\\', "\\\\"))
+                ));
+            }
+        }
+        Dialect::Sqlite => bail!("SQLite keeps no descriptions of columns"),
+    }
+    Ok(statements)
+}
@@ -0,0 +1,471 @@
+// Copyright 2023 Jonathan Bowman
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::infer::geo::WGS84;
+use crate::infer::{SQLType, SQLTypeName};
+use crate::keywords;
+use anyhow::bail;
+use std::str::FromStr;
+
+/// The database the schema and output files are written for
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Dialect {
+    #[default]
+    SqlServer,
+    Postgres,
+    Sqlite,
+    /// MySQL 8, or MariaDB apart from spatial columns
+    Mysql,
+    /// Oracle, whose names are limited to 128 bytes from 12.2 and 30 before
+    Oracle {
+        identifier_length: usize,
+    },
+}
+
+/// What becomes of a column named with a word the dialect reserves
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum IdentifierPolicy {
+    /// keep the name, quoted wherever it is written in SQL
+    Quote,
+    /// put the first letter of the table or file before it, as in `t_order`
+    #[default]
+    Prefix,
+    /// put that letter after it, as in `order_t`
+    Suffix,
+}
+
+impl FromStr for IdentifierPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &s.to_ascii_lowercase()[..] {
+            "quote" => Ok(IdentifierPolicy::Quote),
+            "prefix" => Ok(IdentifierPolicy::Prefix),
+            "suffix" => Ok(IdentifierPolicy::Suffix),
+            _ => bail!(
+                "Unknown identifier policy {:?}, expected quote, prefix or suffix",
+                s
+            ),
+        }
+    }
+}
+
+impl FromStr for Dialect {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &s.to_ascii_lowercase()[..] {
+            "sqlserver" | "mssql" => Ok(Dialect::SqlServer),
+            "postgres" | "postgresql" => Ok(Dialect::Postgres),
+            "sqlite" => Ok(Dialect::Sqlite),
+            "mysql" | "mariadb" => Ok(Dialect::Mysql),
+            "oracle" => Ok(Dialect::Oracle {
+                identifier_length: 128,
+            }),
+            "oracle11" => Ok(Dialect::Oracle {
+                identifier_length: 30,
+            }),
+            _ => bail!(
+                "Unknown dialect {:?}, expected sqlserver, postgres, sqlite, mysql, oracle \
+                or oracle11",
+                s
+            ),
+        }
+    }
+}
+
+impl Dialect {
+    /// Words a column may not be named without renaming or quoting
+    pub fn keywords(self) -> &'static [&'static str] {
+        match self {
+            Dialect::SqlServer => &keywords::KEYWORDS,
+            Dialect::Postgres => &keywords::POSTGRES_KEYWORDS,
+            Dialect::Sqlite => &keywords::SQLITE_KEYWORDS,
+            Dialect::Mysql => &keywords::MYSQL_KEYWORDS,
+            Dialect::Oracle { .. } => &keywords::ORACLE_KEYWORDS,
+        }
+    }
+
+    /// The most bytes a name may have, if there is a limit
+    pub fn identifier_length(self) -> Option<usize> {
+        match self {
+            Dialect::SqlServer => Some(128),
+            Dialect::Postgres => Some(63),
+            Dialect::Sqlite => None,
+            Dialect::Mysql => Some(64),
+            Dialect::Oracle { identifier_length } => Some(identifier_length),
+        }
+    }
+
+    /// The start of a name that leaves room for `reserve` more bytes
+    pub fn truncate(self, name: &str, reserve: usize) -> &str {
+        let Some(limit) = self.identifier_length() else {
+            return name;
+        };
+        let mut end = name.len().min(limit.saturating_sub(reserve));
+        while !name.is_char_boundary(end) {
+            end -= 1;
+        }
+        &name[..end]
+    }
+
+    pub fn is_reserved(self, name: &str) -> bool {
+        self.keywords().contains(&&name.to_ascii_lowercase()[..])
+    }
+
+    /// An identifier as written in SQL, quoted where the dialect needs it
+    pub fn identifier(self, name: &str) -> String {
+        let plain = match self {
+            Dialect::SqlServer => {
+                // a leading `#` marks a temporary table
+                let mut chars = name.chars();
+                chars
+                    .next()
+                    .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '#')
+                    && chars.all(|c| c.is_alphanumeric() || c == '_')
+            }
+            Dialect::Postgres => {
+                name.chars()
+                    .next()
+                    .is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+                    && name
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+            }
+            Dialect::Sqlite | Dialect::Mysql => {
+                name.chars()
+                    .next()
+                    .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                    && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            }
+            Dialect::Oracle { .. } => {
+                name.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+                    && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            }
+        };
+        if plain && !self.is_reserved(name) {
+            name.to_string()
+        } else if self == Dialect::SqlServer {
+            format!("[{}]", name.replace(']', "]]"))
+        } else if self == Dialect::Mysql {
+            format!("`{}`", name.replace('`', "``"))
+        } else {
+            format!("\"{}\"", name.replace('"', "\"\""))
+        }
+    }
+
+    pub fn identifiers(self, names: &[String]) -> Vec<String> {
+        names.iter().map(|name| self.identifier(name)).collect()
+    }
+
+    /// The column type as this database spells it
+    pub fn type_name(self, sqltype: &SQLType) -> String {
+        match self {
+            Dialect::SqlServer => sqltype.to_string(),
+            Dialect::Postgres => postgres_type(sqltype),
+            Dialect::Sqlite => sqlite_type(sqltype),
+            Dialect::Mysql => mysql_type(sqltype),
+            Dialect::Oracle { .. } => oracle_type(sqltype),
+        }
+    }
+
+    /// A constraint checking that a column holds JSON, if the type needs one
+    pub fn json_check(self, column: &str) -> Option<String> {
+        match self {
+            Dialect::SqlServer => Some(format!("CHECK (ISJSON({}) = 1)", column)),
+            Dialect::Postgres | Dialect::Mysql => None,
+            Dialect::Sqlite => Some(format!("CHECK (json_valid({}))", column)),
+            Dialect::Oracle { .. } => Some(format!("CHECK ({} IS JSON)", column)),
+        }
+    }
+}
+
+/// A table name of up to three parts, as in `warehouse.sales.orders`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableName {
+    pub database: Option<String>,
+    pub schema: Option<String>,
+    pub table: String,
+}
+
+/// One part of a table name, quoted or not, and what follows it
+pub(crate) fn name_part(text: &str) -> anyhow::Result<(String, &str)> {
+    let close = match text.chars().next() {
+        Some('[') => ']',
+        Some('"') => '"',
+        Some('`') => '`',
+        _ => {
+            let end = text.find('.').unwrap_or(text.len());
+            return Ok((text[..end].trim().to_string(), &text[end..]));
+        }
+    };
+    let mut part = String::new();
+    let mut chars = text[1..].char_indices();
+    while let Some((i, c)) = chars.next() {
+        if c != close {
+            part.push(c);
+        } else if text[i + 2..].starts_with(close) {
+            // a doubled closing quote stands for itself
+            part.push(c);
+            chars.next();
+        } else {
+            return Ok((part, &text[i + 2..]));
+        }
+    }
+    bail!("Unterminated quote in table name {:?}", text)
+}
+
+impl FromStr for TableName {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = Vec::new();
+        let mut rest = s.trim();
+        loop {
+            let (part, after) = name_part(rest)?;
+            if part.is_empty() {
+                bail!("Empty part in table name {:?}", s);
+            }
+            if part.chars().any(char::is_control) {
+                bail!("Control character in table name {:?}", s);
+            }
+            parts.push(part);
+            match after.trim_start().strip_prefix('.') {
+                Some(next) => rest = next.trim_start(),
+                None if after.trim().is_empty() => break,
+                None => bail!("Unexpected {:?} in table name {:?}", after, s),
+            }
+        }
+        if parts.len() > 3 {
+            bail!("Too many parts in table name {:?}", s);
+        }
+        let table = parts.pop().unwrap_or_default();
+        let schema = parts.pop();
+        let database = parts.pop();
+        Ok(TableName {
+            database,
+            schema,
+            table,
+        })
+    }
+}
+
+impl TableName {
+    fn parts(&self) -> impl Iterator<Item = &str> {
+        [self.database.as_deref(), self.schema.as_deref()]
+            .into_iter()
+            .flatten()
+            .chain([&self.table[..]])
+    }
+
+    /// Put the table in a schema, unless it names one already
+    pub fn or_schema(mut self, schema: Option<&str>) -> Self {
+        if self.schema.is_none() {
+            self.schema = schema.map(str::to_string);
+        }
+        self
+    }
+
+    /// Another table in the same database and schema
+    pub fn sibling(&self, table: &str) -> Self {
+        TableName {
+            table: table.to_string(),
+            ..self.clone()
+        }
+    }
+
+    /// Check the name has no more parts, and no longer parts, than allowed
+    pub fn validate(&self, dialect: Dialect) -> anyhow::Result<()> {
+        let most = match dialect {
+            Dialect::SqlServer | Dialect::Postgres => 3,
+            Dialect::Sqlite | Dialect::Mysql | Dialect::Oracle { .. } => 2,
+        };
+        if self.parts().count() > most {
+            bail!(
+                "Table name {} has more than {} parts",
+                self.qualified(dialect),
+                most
+            );
+        }
+        if let Some(limit) = dialect.identifier_length() {
+            if let Some(part) = self.parts().find(|part| part.len() > limit) {
+                bail!("{:?} is longer than {} bytes", part, limit);
+            }
+        }
+        Ok(())
+    }
+
+    /// The name as written in SQL, each part quoted as the dialect needs
+    pub fn qualified(&self, dialect: Dialect) -> String {
+        self.parts()
+            .map(|part| dialect.identifier(part))
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    /// A file name for the table
+    pub fn file_stem(&self) -> String {
+        self.parts()
+            .map(|part| {
+                part.chars()
+                    .map(|c| {
+                        if c.is_alphanumeric() || c == '-' {
+                            c
+                        } else {
+                            '_'
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+}
+
+/// PostgreSQL keeps at most microseconds
+const POSTGRES_MAX_PRECISION: usize = 6;
+
+fn postgres_type(sqltype: &SQLType) -> String {
+    let precision = sqltype.size.min(POSTGRES_MAX_PRECISION);
+    match sqltype.name {
+        SQLTypeName::Bit => "boolean".to_string(),
+        SQLTypeName::Tinyint | SQLTypeName::Smallint => "smallint".to_string(),
+        SQLTypeName::Int => "integer".to_string(),
+        SQLTypeName::Bigint => "bigint".to_string(),
+        SQLTypeName::Numeric => {
+            format!(
+                "numeric({}, {})",
+                sqltype.size + sqltype.scale,
+                sqltype.scale
+            )
+        }
+        SQLTypeName::Float if sqltype.size > 0 && sqltype.size <= 24 => "real".to_string(),
+        SQLTypeName::Float => "double precision".to_string(),
+        SQLTypeName::Date => "date".to_string(),
+        SQLTypeName::Time => format!("time({})", precision),
+        SQLTypeName::Datetime2 => format!("timestamp({})", precision),
+        SQLTypeName::Datetimeoffset => format!("timestamptz({})", precision),
+        SQLTypeName::Duration => "interval".to_string(),
+        SQLTypeName::Json => "jsonb".to_string(),
+        SQLTypeName::Xml => "xml".to_string(),
+        SQLTypeName::Geography => "geography".to_string(),
+        SQLTypeName::Geometry => "geometry".to_string(),
+        SQLTypeName::Binary | SQLTypeName::Varbinary => "bytea".to_string(),
+        SQLTypeName::Char => format!("char({})", sqltype.size),
+        SQLTypeName::Varchar => format!("varchar({})", sqltype.size),
+        SQLTypeName::Varcharmax => "text".to_string(),
+        SQLTypeName::Custom(name) => name.to_string(),
+    }
+}
+
+/// The type affinity SQLite gives a column
+fn sqlite_type(sqltype: &SQLType) -> String {
+    let affinity = match sqltype.name {
+        SQLTypeName::Bit
+        | SQLTypeName::Tinyint
+        | SQLTypeName::Smallint
+        | SQLTypeName::Int
+        | SQLTypeName::Bigint
+        | SQLTypeName::Duration => "INTEGER",
+        SQLTypeName::Numeric => "NUMERIC",
+        SQLTypeName::Float => "REAL",
+        SQLTypeName::Binary | SQLTypeName::Varbinary => "BLOB",
+        SQLTypeName::Custom(name) => name,
+        _ => "TEXT",
+    };
+    affinity.to_string()
+}
+
+/// MySQL keeps at most microseconds, and CHAR and BINARY hold at most 255
+const MYSQL_MAX_PRECISION: usize = 6;
+const MYSQL_MAX_FIXED: usize = 255;
+
+/// The column type as MySQL spells it
+fn mysql_type(sqltype: &SQLType) -> String {
+    let precision = sqltype.size.min(MYSQL_MAX_PRECISION);
+    match sqltype.name {
+        SQLTypeName::Bit => "TINYINT(1)".to_string(),
+        SQLTypeName::Tinyint => "TINYINT UNSIGNED".to_string(),
+        SQLTypeName::Smallint => "SMALLINT".to_string(),
+        SQLTypeName::Int => "INT".to_string(),
+        SQLTypeName::Bigint | SQLTypeName::Duration => "BIGINT".to_string(),
+        SQLTypeName::Numeric => {
+            format!(
+                "DECIMAL({}, {})",
+                sqltype.size + sqltype.scale,
+                sqltype.scale
+            )
+        }
+        SQLTypeName::Float if sqltype.size > 0 && sqltype.size <= 24 => "FLOAT".to_string(),
+        SQLTypeName::Float => "DOUBLE".to_string(),
+        SQLTypeName::Date => "DATE".to_string(),
+        SQLTypeName::Time => format!("TIME({})", precision),
+        SQLTypeName::Datetime2 | SQLTypeName::Datetimeoffset => {
+            format!("DATETIME({})", precision)
+        }
+        SQLTypeName::Json => "JSON".to_string(),
+        SQLTypeName::Geography => format!("GEOMETRY SRID {}", WGS84),
+        SQLTypeName::Geometry => "GEOMETRY".to_string(),
+        SQLTypeName::Binary if sqltype.size <= MYSQL_MAX_FIXED => {
+            format!("BINARY({})", sqltype.size)
+        }
+        SQLTypeName::Binary | SQLTypeName::Varbinary => format!("VARBINARY({})", sqltype.size),
+        SQLTypeName::Char if sqltype.size <= MYSQL_MAX_FIXED => format!("CHAR({})", sqltype.size),
+        SQLTypeName::Char | SQLTypeName::Varchar => format!("VARCHAR({})", sqltype.size),
+        SQLTypeName::Xml | SQLTypeName::Varcharmax => "LONGTEXT".to_string(),
+        SQLTypeName::Custom(name) => name.to_string(),
+    }
+}
+
+/// Oracle's limit on fractional seconds digits
+const ORACLE_MAX_PRECISION: usize = 9;
+const ORACLE_MAX_CHAR: usize = 2000;
+const ORACLE_MAX_VARCHAR: usize = 4000;
+const ORACLE_MAX_RAW: usize = 2000;
+
+/// The column type as Oracle spells it
+fn oracle_type(sqltype: &SQLType) -> String {
+    let precision = sqltype.size.min(ORACLE_MAX_PRECISION);
+    match sqltype.name {
+        SQLTypeName::Bit => "NUMBER(1)".to_string(),
+        SQLTypeName::Tinyint => "NUMBER(3)".to_string(),
+        SQLTypeName::Smallint => "NUMBER(5)".to_string(),
+        SQLTypeName::Int => "NUMBER(10)".to_string(),
+        SQLTypeName::Bigint | SQLTypeName::Duration => "NUMBER(19)".to_string(),
+        SQLTypeName::Numeric => {
+            format!(
+                "NUMBER({}, {})",
+                sqltype.size + sqltype.scale,
+                sqltype.scale
+            )
+        }
+        SQLTypeName::Float if sqltype.size > 0 && sqltype.size <= 24 => "BINARY_FLOAT".to_string(),
+        SQLTypeName::Float => "BINARY_DOUBLE".to_string(),
+        SQLTypeName::Date => "DATE".to_string(),
+        SQLTypeName::Time => format!("INTERVAL DAY(0) TO SECOND({})", precision),
+        SQLTypeName::Datetime2 => format!("TIMESTAMP({})", precision),
+        SQLTypeName::Datetimeoffset => format!("TIMESTAMP({}) WITH TIME ZONE", precision),
+        SQLTypeName::Binary | SQLTypeName::Varbinary if sqltype.size <= ORACLE_MAX_RAW => {
+            format!("RAW({})", sqltype.size.max(1))
+        }
+        SQLTypeName::Binary | SQLTypeName::Varbinary => "BLOB".to_string(),
+        SQLTypeName::Char if sqltype.size <= ORACLE_MAX_CHAR => {
+            format!("CHAR({} CHAR)", sqltype.size)
+        }
+        SQLTypeName::Char | SQLTypeName::Varchar if sqltype.size <= ORACLE_MAX_VARCHAR => {
+            format!("VARCHAR2({} CHAR)", sqltype.size)
+        }
+        SQLTypeName::Char
+        | SQLTypeName::Varchar
+        | SQLTypeName::Varcharmax
+        | SQLTypeName::Json
+        | SQLTypeName::Xml
+        | SQLTypeName::Geography
+        | SQLTypeName::Geometry => "CLOB".to_string(),
+        SQLTypeName::Custom(name) => name.to_string(),
+    }
+}
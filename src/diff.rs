@@ -0,0 +1,492 @@
+// Copyright 2023 Jonathan Bowman
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::dialect::{self, Dialect, TableName};
+use crate::infer::{SQLType, SQLTypeName};
+use crate::CsvStats;
+use anyhow::{bail, Context, Result};
+use std::iter::zip;
+
+/// Statements that name table-level constraints rather than a column
+const CONSTRAINTS: [&str; 7] = [
+    "CONSTRAINT",
+    "PRIMARY",
+    "UNIQUE",
+    "CHECK",
+    "FOREIGN",
+    "INDEX",
+    "PERIOD",
+];
+
+/// A column of the existing table
+#[derive(Clone, Debug)]
+pub struct Column {
+    pub name: String,
+    /// the type as the table definition writes it
+    pub definition: String,
+    /// `None` for a type that is never inferred
+    pub sqltype: Option<SQLType>,
+    pub not_null: bool,
+}
+
+/// How an existing column's type compares with the inferred one
+#[derive(Clone, Debug)]
+enum Change {
+    /// the column holds every value as it is
+    Keep,
+    /// the column holds every value, though a narrower type would too
+    Narrower,
+    /// the column must be widened to this type of the same kind
+    Widen(SQLType),
+    /// the values need a different kind of type
+    Incompatible(SQLType),
+}
+
+/// Byte offsets of a top-level character and of each top-level closing parenthesis
+fn top_level(text: &str, target: char) -> Vec<usize> {
+    let mut found = Vec::new();
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if let Some(close) = quote {
+            // a doubled closing quote stands for the character itself
+            if c == close && chars.next_if(|&(_, next)| next == close).is_none() {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '[' => quote = Some(']'),
+            '"' | '\'' | '`' => quote = Some(c),
+            '(' => {
+                if depth == 0 && c == target {
+                    found.push(i);
+                }
+                depth += 1;
+            }
+            ')' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 && c == target {
+                    found.push(i);
+                }
+            }
+            _ if depth == 0 && c == target => found.push(i),
+            _ => {}
+        }
+    }
+    found
+}
+
+/// The type of an existing SQL Server column
+fn existing_type(word: &str, args: &[&str]) -> Option<SQLType> {
+    let mut word = word.to_ascii_lowercase();
+    // national character types hold the same text, counted in characters
+    if word == "nchar" || word == "nvarchar" {
+        word.remove(0);
+    }
+    let max = args
+        .first()
+        .is_some_and(|arg| arg.eq_ignore_ascii_case("max"));
+    let number = |i: usize| args.get(i).and_then(|arg| arg.parse::<usize>().ok());
+    let (name, size, scale) = match &word[..] {
+        "varchar" if max => (SQLTypeName::Varcharmax, 0, 0),
+        "datetime" => (SQLTypeName::Datetime2, 3, 0),
+        "smalldatetime" => (SQLTypeName::Datetime2, 0, 0),
+        "real" => (SQLTypeName::Float, 24, 0),
+        "float" => {
+            let bits = if number(0).is_some_and(|n| n <= 24) {
+                24
+            } else {
+                53
+            };
+            (SQLTypeName::Float, bits, 0)
+        }
+        "numeric" | "decimal" => {
+            let precision = number(0).unwrap_or(18);
+            let scale = number(1).unwrap_or(0);
+            (SQLTypeName::Numeric, precision.saturating_sub(scale), scale)
+        }
+        _ => {
+            let name = word.parse::<SQLTypeName>().ok()?;
+            let size = match name {
+                SQLTypeName::Binary | SQLTypeName::Varbinary if max => usize::MAX,
+                SQLTypeName::Char
+                | SQLTypeName::Varchar
+                | SQLTypeName::Binary
+                | SQLTypeName::Varbinary => number(0)?.max(1),
+                SQLTypeName::Time | SQLTypeName::Datetime2 | SQLTypeName::Datetimeoffset => {
+                    number(0).unwrap_or(7)
+                }
+                _ => 0,
+            };
+            (name, size, 0)
+        }
+    };
+    Some(SQLType {
+        name,
+        size,
+        scale,
+        ..Default::default()
+    })
+}
+
+/// One column definition, or `None` for a constraint or computed column
+fn column(definition: &str) -> Result<Option<Column>> {
+    let definition = definition.trim();
+    let first = definition
+        .split(|c: char| c.is_whitespace() || c == '(')
+        .next()
+        .unwrap_or_default();
+    if CONSTRAINTS.iter().any(|k| first.eq_ignore_ascii_case(k)) {
+        return Ok(None);
+    }
+    let (name, rest) = if definition.starts_with(['[', '"', '`']) {
+        dialect::name_part(definition)?
+    } else {
+        let end = definition
+            .find(char::is_whitespace)
+            .unwrap_or(definition.len());
+        (definition[..end].to_string(), &definition[end..])
+    };
+    let rest = rest.trim_start();
+    let word_end = rest
+        .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .unwrap_or(rest.len());
+    let word = &rest[..word_end];
+    if word.is_empty() || word.eq_ignore_ascii_case("AS") {
+        // computed from other columns, so never loaded
+        return Ok(None);
+    }
+    let after = &rest[word_end..];
+    let (args, after) = match after.trim_start().strip_prefix('(') {
+        Some(inside) => {
+            let Some(close) = inside.find(')') else {
+                bail!("Unclosed size in column definition {:?}", definition);
+            };
+            (&inside[..close], &inside[close + 1..])
+        }
+        None => ("", after),
+    };
+    let args: Vec<&str> = args
+        .split(',')
+        .map(str::trim)
+        .filter(|arg| !arg.is_empty())
+        .collect();
+    let definition = if args.is_empty() {
+        word.to_string()
+    } else {
+        format!("{}({})", word, args.join(", "))
+    };
+    let not_null = after
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .windows(2)
+        .any(|pair| pair[0].eq_ignore_ascii_case("NOT") && pair[1].eq_ignore_ascii_case("NULL"));
+    Ok(Some(Column {
+        name,
+        sqltype: existing_type(word, &args),
+        definition,
+        not_null,
+    }))
+}
+
+/// The table and columns of the first `CREATE TABLE` statement
+pub fn parse_create_table(text: &str) -> Result<(TableName, Vec<Column>)> {
+    let Some(start) = text.to_ascii_uppercase().find("CREATE TABLE") else {
+        bail!("No CREATE TABLE statement found");
+    };
+    let mut rest = text[start + "CREATE TABLE".len()..].trim_start();
+    if rest
+        .get(..13)
+        .is_some_and(|s| s.eq_ignore_ascii_case("IF NOT EXISTS"))
+    {
+        rest = rest[13..].trim_start();
+    }
+    let Some(&open) = top_level(rest, '(').first() else {
+        bail!("No column definitions in CREATE TABLE statement");
+    };
+    let table: TableName = rest[..open].trim().parse()?;
+    let Some(&close) = top_level(&rest[open..], ')').first() else {
+        bail!(
+            "Unclosed column definitions for table {}",
+            rest[..open].trim()
+        );
+    };
+    let body = &rest[open + 1..open + close];
+    let mut columns = Vec::new();
+    let mut from = 0;
+    for end in top_level(body, ',').into_iter().chain([body.len()]) {
+        let definition = &body[from..end];
+        from = end + 1;
+        if let Some(column) = column(definition)
+            .with_context(|| format!("Failed to read table {}", rest[..open].trim()))?
+        {
+            columns.push(column);
+        }
+    }
+    Ok((table, columns))
+}
+
+/// The columns of a table created from a state file saved by `save`
+pub fn state_columns(stats: &CsvStats) -> Result<Vec<Column>> {
+    let Some(sqltypes) = &stats.column_types else {
+        bail!("No column types were inferred in the state file");
+    };
+    Ok(zip(&stats.columns, sqltypes)
+        .map(|(name, sqltype)| Column {
+            name: name.clone(),
+            definition: sqltype.to_string(),
+            sqltype: Some(sqltype.clone()),
+            not_null: sqltype.not_null,
+        })
+        .collect())
+}
+
+/// Whole digits an integer type holds
+fn digits(name: SQLTypeName) -> usize {
+    match name {
+        SQLTypeName::Bit => 1,
+        SQLTypeName::Tinyint => 3,
+        SQLTypeName::Smallint => 5,
+        SQLTypeName::Int => 10,
+        _ => 19,
+    }
+}
+
+fn integer(name: SQLTypeName) -> bool {
+    name >= SQLTypeName::Bit && name <= SQLTypeName::Bigint
+}
+
+fn temporal(name: SQLTypeName) -> bool {
+    matches!(
+        name,
+        SQLTypeName::Date | SQLTypeName::Datetime2 | SQLTypeName::Datetimeoffset
+    )
+}
+
+fn text(name: SQLTypeName) -> bool {
+    matches!(
+        name,
+        SQLTypeName::Char | SQLTypeName::Varchar | SQLTypeName::Varcharmax
+    )
+}
+
+fn binary(name: SQLTypeName) -> bool {
+    matches!(name, SQLTypeName::Binary | SQLTypeName::Varbinary)
+}
+
+fn sized(name: SQLTypeName, size: usize, scale: usize) -> SQLType {
+    SQLType {
+        name,
+        size,
+        scale,
+        ..Default::default()
+    }
+}
+
+/// Keep the column as it is if its size holds the values, or widen it
+fn grow(existing: &SQLType, name: SQLTypeName, size: usize, scale: usize) -> Change {
+    if name == existing.name && size <= existing.size && scale <= existing.scale {
+        Change::Keep
+    } else {
+        Change::Widen(sized(
+            name,
+            size.max(existing.size),
+            scale.max(existing.scale),
+        ))
+    }
+}
+
+/// Compare the type of an existing column with the inferred one
+fn compare(existing: &SQLType, inferred: &SQLType) -> Change {
+    let (from, to) = (existing.name, inferred.name);
+    if inferred.byte_length == 0 {
+        // no values to hold
+        return Change::Keep;
+    }
+    if from == SQLTypeName::Varcharmax {
+        return if text(to) {
+            Change::Keep
+        } else {
+            Change::Narrower
+        };
+    }
+    let narrower = |keep: Change| {
+        if matches!(keep, Change::Keep) && from != to {
+            Change::Narrower
+        } else {
+            keep
+        }
+    };
+    match (from, to) {
+        (SQLTypeName::Char | SQLTypeName::Varchar, _) => {
+            let length = inferred.byte_length;
+            if length <= existing.size {
+                if text(to) {
+                    Change::Keep
+                } else {
+                    Change::Narrower
+                }
+            } else if length > 8000 {
+                Change::Widen(sized(SQLTypeName::Varcharmax, 0, 0))
+            } else if from == SQLTypeName::Char && to == SQLTypeName::Char {
+                Change::Widen(sized(SQLTypeName::Char, length, 0))
+            } else {
+                Change::Widen(sized(SQLTypeName::Varchar, length, 0))
+            }
+        }
+        _ if integer(from) && integer(to) => narrower(if to <= from {
+            Change::Keep
+        } else {
+            Change::Widen(sized(to, 0, 0))
+        }),
+        _ if integer(from) && to == SQLTypeName::Numeric => Change::Widen(sized(
+            SQLTypeName::Numeric,
+            inferred.size.max(digits(from)),
+            inferred.scale,
+        )),
+        (SQLTypeName::Numeric, _) if integer(to) || to == SQLTypeName::Numeric => {
+            let size = if integer(to) {
+                digits(to)
+            } else {
+                inferred.size
+            };
+            if size + existing.scale.max(inferred.scale) > 38 {
+                return Change::Incompatible(sized(SQLTypeName::Float, 53, 0));
+            }
+            narrower(grow(existing, from, size, inferred.scale))
+        }
+        _ if (integer(from) || from == SQLTypeName::Numeric) && to == SQLTypeName::Float => {
+            Change::Widen(sized(to, inferred.size, 0))
+        }
+        (SQLTypeName::Float, _) if integer(to) || to == SQLTypeName::Numeric => Change::Narrower,
+        (SQLTypeName::Float, SQLTypeName::Float) => grow(existing, from, inferred.size, 0),
+        _ if temporal(from) && temporal(to) => {
+            if to <= from {
+                narrower(
+                    if to == SQLTypeName::Date || inferred.size <= existing.size {
+                        Change::Keep
+                    } else {
+                        Change::Widen(sized(from, inferred.size, 0))
+                    },
+                )
+            } else {
+                let size = if from == SQLTypeName::Date {
+                    inferred.size
+                } else {
+                    inferred.size.max(existing.size)
+                };
+                Change::Widen(sized(to, size, 0))
+            }
+        }
+        (SQLTypeName::Time, SQLTypeName::Time) => grow(existing, from, inferred.size, 0),
+        _ if binary(from) && binary(to) => {
+            let name = if from == SQLTypeName::Binary && to == SQLTypeName::Binary {
+                from
+            } else {
+                SQLTypeName::Varbinary
+            };
+            if inferred.size <= existing.size {
+                Change::Keep
+            } else {
+                Change::Widen(sized(name, inferred.size, 0))
+            }
+        }
+        (SQLTypeName::Geometry, SQLTypeName::Geography) => Change::Keep,
+        _ if from == to => Change::Keep,
+        _ => Change::Incompatible(inferred.clone()),
+    }
+}
+
+/// The SQL Server type for a changed column, national if the column was
+fn changed_type(found: &Column, sqltype: &SQLType) -> String {
+    let name = Dialect::SqlServer.type_name(sqltype);
+    let word = found.definition.split('(').next().unwrap_or_default();
+    let national = word.eq_ignore_ascii_case("nchar") || word.eq_ignore_ascii_case("nvarchar");
+    if !national || !text(sqltype.name) {
+        name
+    } else if sqltype.name == SQLTypeName::Varcharmax || sqltype.size > 4000 {
+        "NVARCHAR(MAX)".to_string()
+    } else {
+        format!("N{}", name)
+    }
+}
+
+/// The `ALTER TABLE` statements fitting an existing table to the inferred columns
+pub fn alter_statements(
+    table: &TableName,
+    existing: &[Column],
+    columns: &[String],
+    sqltypes: &[SQLType],
+) -> String {
+    let dialect = Dialect::SqlServer;
+    let name = table.qualified(dialect);
+    let mut statements = String::new();
+    for (column, sqltype) in zip(columns, sqltypes) {
+        let Some(found) = existing
+            .iter()
+            .find(|found| found.name.eq_ignore_ascii_case(column))
+        else {
+            statements.push_str(&format!(
+                "ALTER TABLE {} ADD {} {};\n",
+                name,
+                dialect.identifier(column),
+                dialect.type_name(sqltype)
+            ));
+            continue;
+        };
+        let identifier = dialect.identifier(&found.name);
+        let Some(current) = &found.sqltype else {
+            statements.push_str(&format!(
+                "-- {} {} is not a type that is inferred; not compared\n",
+                identifier, found.definition
+            ));
+            continue;
+        };
+        let not_null = if found.not_null { " NOT NULL" } else { "" };
+        match compare(current, sqltype) {
+            Change::Keep => {}
+            Change::Narrower => statements.push_str(&format!(
+                "-- {} {} is wider than the data needs, which fits {}\n",
+                identifier,
+                found.definition,
+                dialect.type_name(sqltype)
+            )),
+            Change::Widen(wider) => statements.push_str(&format!(
+                "ALTER TABLE {} ALTER COLUMN {} {}{};\n",
+                name,
+                identifier,
+                changed_type(found, &wider),
+                not_null
+            )),
+            Change::Incompatible(other) => statements.push_str(&format!(
+                "-- {} {} cannot hold the data, which needs {}, without converting its rows\n\
+                -- ALTER TABLE {} ALTER COLUMN {} {}{};\n",
+                identifier,
+                found.definition,
+                changed_type(found, &other),
+                name,
+                identifier,
+                changed_type(found, &other),
+                not_null
+            )),
+        }
+    }
+    for found in existing {
+        if !columns.iter().any(|c| c.eq_ignore_ascii_case(&found.name)) {
+            statements.push_str(&format!(
+                "-- {} is in the table but not in the file\n",
+                dialect.identifier(&found.name)
+            ));
+        }
+    }
+    if statements.is_empty() {
+        statements = format!("-- {} already holds the data\n", name);
+    }
+    statements
+}
@@ -6,12 +6,27 @@
 // copied, modified, or distributed except according to those terms.
 
 use atoi::atoi;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use simdutf8::basic::from_utf8;
 use std::fmt;
-use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
-
+use std::str::FromStr;
+use time::format_description::FormatItem;
+use time::parsing::Parsed;
+use time::{Date, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+pub mod binary;
+pub mod duration;
+pub mod epoch;
+pub mod geo;
+pub mod limits;
+mod registry;
 mod timeformats;
 
+pub use registry::{Registry, TypeCheck};
+
+/// Expand a two-digit year as SQL Server does by default
+const TWO_DIGIT_YEAR_CUTOFF: i32 = 2049;
+
 #[derive(Clone, Copy)]
 struct ByteText<'a> {
     bytes: &'a [u8],
@@ -35,7 +50,7 @@ impl<'a> ByteText<'a> {
     }
 }
 
-#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
 pub enum SQLTypeName {
     #[default]
     Bit,
@@ -49,87 +64,189 @@ pub enum SQLTypeName {
     Time,
     Datetime2,
     Datetimeoffset,
+    /// A length of time, stored as seconds or TIME once inference is done
+    Duration,
+    Json,
+    Xml,
+    Geography,
+    Geometry,
+    Binary,
+    Varbinary,
     Char,
     Varchar,
     Varcharmax,
+    /// A type produced by a custom [`TypeCheck`], written in DDL as given
+    Custom(&'static str),
 }
 
-type Check = fn(ByteText, usize) -> Option<SQLType>;
-
-const CHECKS: [&Check; 15] = [
-    &(check_bit as Check),
-    &(check_tinyint as Check),
-    &(check_smallint as Check),
-    &(check_int as Check),
-    &(check_bigint as Check),
-    &(check_decimal as Check),
-    &(check_real as Check),
-    &(check_float as Check),
-    &(check_date as Check),
-    &(check_time as Check),
-    &(check_datetime as Check),
-    &(check_datetimeoffset as Check),
-    &(check_char as Check),
-    &(check_varchar as Check),
-    &(check_varcharmax as Check),
-];
+impl FromStr for SQLTypeName {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = match &s.trim().to_ascii_lowercase()[..] {
+            "bit" => SQLTypeName::Bit,
+            "tinyint" => SQLTypeName::Tinyint,
+            "smallint" => SQLTypeName::Smallint,
+            "int" | "integer" => SQLTypeName::Int,
+            "bigint" => SQLTypeName::Bigint,
+            "numeric" | "decimal" => SQLTypeName::Numeric,
+            "float" => SQLTypeName::Float,
+            "date" => SQLTypeName::Date,
+            "time" => SQLTypeName::Time,
+            "datetime2" => SQLTypeName::Datetime2,
+            "datetimeoffset" => SQLTypeName::Datetimeoffset,
+            "json" => SQLTypeName::Json,
+            "xml" => SQLTypeName::Xml,
+            "geography" => SQLTypeName::Geography,
+            "geometry" => SQLTypeName::Geometry,
+            "binary" => SQLTypeName::Binary,
+            "varbinary" => SQLTypeName::Varbinary,
+            "char" => SQLTypeName::Char,
+            "varchar" => SQLTypeName::Varchar,
+            "varchar(max)" | "varcharmax" => SQLTypeName::Varcharmax,
+            _ => anyhow::bail!("Unknown SQL type {:?}", s),
+        };
+        Ok(name)
+    }
+}
 
-#[derive(Clone, Debug, Default)]
+/// Saved by the lowercase name [`FromStr`] reads back
+impl Serialize for SQLTypeName {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            SQLTypeName::Custom(name) => Err(serde::ser::Error::custom(format!(
+                "custom type {} cannot be saved",
+                name
+            ))),
+            _ => serializer.serialize_str(&format!("{:?}", self).to_ascii_lowercase()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SQLTypeName {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct SQLType {
     pub name: SQLTypeName,
     pub size: usize,
+    /// position of the check in the registry, not saved
+    #[serde(skip)]
     pub index: usize,
     pub subindex: usize,
     pub scale: usize,
     pub byte_length: usize,
+    pub epoch: Option<epoch::EpochUnit>,
+    pub not_null: bool,
+    /// some value was a number beyond what BIGINT or NUMERIC holds exactly
+    pub out_of_range: bool,
+    /// values are durations, to be written as the type's seconds or time
+    #[serde(default)]
+    pub duration: bool,
 }
 
 impl SQLType {
+    /// Fold the type of another value into this column
     pub fn merge(&mut self, other: &Self) {
-        self.byte_length = other.byte_length.max(self.byte_length);
-        if self.name == other.name && other.name == SQLTypeName::Char && other.size != self.size {
-            self.name = SQLTypeName::Varchar;
-            self.size = other.size.max(self.size);
-            self.index += 1;
-        } else if self.name == other.name {
-            self.subindex = other.subindex.max(self.subindex);
-            self.size = other.size.max(self.size);
-            self.scale = other.scale.max(self.scale);
-        } else if self.index < other.index {
-            if other.name == SQLTypeName::Char {
-                self.size = other.size.max(self.byte_length);
-            } else {
-                self.size = other.size;
-            }
-            self.name = other.name;
-            self.index = other.index;
-            self.subindex = other.subindex;
-            self.scale = other.scale;
+        Registry::builtin().merge(self, other);
+    }
+
+    /// Point `index` at the built-in check for the type
+    pub fn reindex(&mut self) {
+        self.index = Registry::builtin().position(self.name);
+    }
+
+    /// Types whose values are read no other way
+    fn exclusive(&self) -> Option<SQLTypeName> {
+        match self.name {
+            SQLTypeName::Json | SQLTypeName::Xml => Some(self.name),
+            SQLTypeName::Geography | SQLTypeName::Geometry => Some(SQLTypeName::Geometry),
+            SQLTypeName::Binary | SQLTypeName::Varbinary => Some(SQLTypeName::Binary),
+            _ => None,
         }
     }
 
+    /// Treat an integer column as Unix epoch timestamps in the given unit
+    pub fn set_epoch(&mut self, unit: epoch::EpochUnit) {
+        self.name = SQLTypeName::Datetime2;
+        self.size = unit.precision();
+        self.scale = 0;
+        self.subindex = 0;
+        self.epoch = Some(unit);
+    }
+
     pub fn varchar(&self) -> String {
-        if self.name == SQLTypeName::Varcharmax {
+        if self.name == SQLTypeName::Varcharmax || self.byte_length > 8000 {
             "VARCHAR(MAX)".to_string()
         } else {
             format!(
                 "VARCHAR({})",
-                if self.byte_length == 0 {
-                    1
-                } else {
-                    self.byte_length
-                }
+                self.byte_length.max(self.iso8601_length()).max(1)
             )
         }
     }
+
+    /// Length of a value of this type once rewritten by [`iso8601`]
+    fn iso8601_length(&self) -> usize {
+        let fraction = if self.size > 0 { self.size + 1 } else { 0 };
+        match self.name {
+            SQLTypeName::Date => 10,
+            SQLTypeName::Time => 8 + fraction,
+            SQLTypeName::Datetime2 => 19 + fraction,
+            SQLTypeName::Datetimeoffset => 25 + fraction,
+            _ => 0,
+        }
+    }
+}
+
+/// Why a column ended up with its type
+#[derive(Clone, Debug, Default)]
+pub struct Explanation {
+    pub promotions: Vec<Promotion>,
+    pub longest: Option<(u64, String)>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Promotion {
+    pub line: u64,
+    pub value: String,
+    pub from: SQLType,
+    pub to: SQLType,
+}
+
+impl Explanation {
+    /// Note a value that changed the column's type or is the longest so far
+    pub fn record(&mut self, line: u64, value: &[u8], before: &SQLType, after: &SQLType) {
+        if before.name != after.name {
+            self.promotions.push(Promotion {
+                line,
+                value: String::from_utf8_lossy(value).into_owned(),
+                from: before.clone(),
+                to: after.clone(),
+            });
+        }
+        if !value.is_empty()
+            && self
+                .longest
+                .as_ref()
+                .is_none_or(|(_, longest)| value.len() > longest.len())
+        {
+            self.longest = Some((line, String::from_utf8_lossy(value).into_owned()));
+        }
+    }
 }
 
 impl fmt::Display for SQLType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut name = if self.name == SQLTypeName::Varcharmax {
-            "VARCHAR(MAX)".to_string()
-        } else {
-            format!("{:?}", self.name).to_ascii_uppercase()
+        let mut name = match self.name {
+            SQLTypeName::Varcharmax => "VARCHAR(MAX)".to_string(),
+            SQLTypeName::Json => "NVARCHAR(MAX)".to_string(),
+            SQLTypeName::Custom(name) => name.to_string(),
+            _ => format!("{:?}", self.name).to_ascii_uppercase(),
         };
         let size = self.size + self.scale;
         if self.size > 0 || name.contains("TIME") {
@@ -143,30 +260,37 @@ impl fmt::Display for SQLType {
     }
 }
 
-pub fn infer(value: &[u8], mut index: usize, subindex: usize) -> Option<SQLType> {
-    if value.is_empty() {
-        return Some(SQLType {
-            ..Default::default()
-        });
-    }
-    let value = ByteText::new(value);
-    let byte_length = value.bytes.len();
-    while index < CHECKS.len() {
-        let fun = CHECKS[index];
+/// The type of a single value, trying the built-in checks from `index` upward
+pub fn infer(value: &[u8], index: usize, subindex: usize) -> Option<SQLType> {
+    Registry::builtin().infer(value, index, subindex)
+}
 
-        if let Some(mut typesize) = fun(value, subindex) {
-            typesize.index = index;
-            typesize.byte_length = byte_length;
-            return Some(typesize);
-        } else {
-            index += 1;
-        }
+/// Like [`infer`], but a number with leading zeros is still a number
+pub fn infer_number(value: &[u8], index: usize, subindex: usize) -> Option<SQLType> {
+    Registry::builtin().infer_number(value, index, subindex)
+}
+
+/// A zero-padded number without its padding, or `None` if it has none
+fn unpadded(value: &[u8]) -> Option<Vec<u8>> {
+    let trimmed = trim(value);
+    let digits = signed(trimmed);
+    if !zero_padded(digits) {
+        return None;
     }
-    None
+    let start = digits.iter().position(|&c| c != b'0').unwrap_or(0);
+    // keep the zero in front of a decimal point
+    let start = if digits[start] == b'.' {
+        start - 1
+    } else {
+        start
+    };
+    let mut unpadded = trimmed[..trimmed.len() - digits.len()].to_vec();
+    unpadded.extend_from_slice(&digits[start..]);
+    Some(unpadded)
 }
 
 fn zero_padded(value: &[u8]) -> bool {
-    value.len() > 0 && value[0] == b'0' && value.iter().any(|&x| x != b'0' && x != b'.') && (value.len() == 1 || value[1] != b'.')
+    !value.is_empty() && value[0] == b'0' && value.iter().any(|&x| x != b'0' && x != b'.') && (value.len() == 1 || value[1] != b'.')
 }
 
 fn check_bit(value: ByteText, _subindex: usize) -> Option<SQLType> {
@@ -174,7 +298,7 @@ fn check_bit(value: ByteText, _subindex: usize) -> Option<SQLType> {
     if !value.iter().all(u8::is_ascii_digit) {
         return None;
     }
-    let bit = atoi::<i8>(value).unwrap_or_else(|| -1);
+    let bit = atoi::<i8>(value).unwrap_or(-1);
     if bit == 1 || bit == 0 {
         Some(SQLType {
             ..Default::default()
@@ -186,7 +310,7 @@ fn check_bit(value: ByteText, _subindex: usize) -> Option<SQLType> {
 
 fn check_tinyint(value: ByteText, _subindex: usize) -> Option<SQLType> {
     let value = trim(value.bytes);
-    if zero_padded(&value) {
+    if zero_padded(value) {
         return None;
     }
     if value.iter().all(u8::is_ascii_digit) && atoi::<u8>(value).is_some() {
@@ -201,7 +325,7 @@ fn check_tinyint(value: ByteText, _subindex: usize) -> Option<SQLType> {
 
 fn check_smallint(value: ByteText, _subindex: usize) -> Option<SQLType> {
     let value = trim(value.bytes);
-    if zero_padded(&value) {
+    if zero_padded(value) {
         return None;
     }
     let value = signed(value);
@@ -217,7 +341,7 @@ fn check_smallint(value: ByteText, _subindex: usize) -> Option<SQLType> {
 
 fn check_int(value: ByteText, _subindex: usize) -> Option<SQLType> {
     let value = trim(value.bytes);
-    if zero_padded(&value) {
+    if zero_padded(value) {
         return None;
     }
     let value = signed(value);
@@ -233,7 +357,7 @@ fn check_int(value: ByteText, _subindex: usize) -> Option<SQLType> {
 
 fn check_bigint(value: ByteText, _subindex: usize) -> Option<SQLType> {
     let value = trim(value.bytes);
-    if zero_padded(&value) {
+    if zero_padded(value) {
         return None;
     }
     let value = signed(value);
@@ -248,14 +372,14 @@ fn check_bigint(value: ByteText, _subindex: usize) -> Option<SQLType> {
 }
 
 fn signed(value: &[u8]) -> &[u8] {
-    if value.len() > 0 && value[0] == b'-' {
+    if !value.is_empty() && value[0] == b'-' {
         &value[1..]
     } else {
         value
     }
 }
 
-fn trim(value: &[u8]) -> &[u8] {
+pub(crate) fn trim(value: &[u8]) -> &[u8] {
     let from = match value.iter().position(|x| !x.is_ascii_whitespace()) {
         Some(i) => i,
         None => return &value[0..0],
@@ -269,18 +393,15 @@ fn trim(value: &[u8]) -> &[u8] {
 
 fn check_decimal(value: ByteText, _subindex: usize) -> Option<SQLType> {
     let value = trim(value.bytes);
-    if zero_padded(&value) {
+    if zero_padded(value) {
         return None;
     }
     let length = value.iter().filter(|c| c.is_ascii_digit()).count();
     let value = signed(value);
     if value.iter().filter(|c| **c == b'.').count() <= 1
         && value != b"."
-        && value.iter().all(|c| match c {
-            b'.' | b'0' | b'1' | b'2' | b'3' | b'4' | b'5' | b'6' | b'7' | b'8' | b'9' => true,
-            _ => false,
-        })
-        && length <= 38
+        && value.iter().all(|c| matches!(c, b'.' | b'0'..=b'9'))
+        && length <= limits::MAX_PRECISION
     {
         if let Some(point) = value.iter().position(|&x| x == b'.') {
             let scale = value[point + 1..]
@@ -290,13 +411,14 @@ fn check_decimal(value: ByteText, _subindex: usize) -> Option<SQLType> {
             Some(SQLType {
                 name: SQLTypeName::Numeric,
                 size: length - scale,
-                scale: scale,
+                scale,
                 ..Default::default()
             })
         } else {
             Some(SQLType {
                 name: SQLTypeName::Numeric,
                 size: length,
+                out_of_range: limits::beyond_bigint(value),
                 ..Default::default()
             })
         }
@@ -309,12 +431,14 @@ fn check_real(mut value: ByteText, _subindex: usize) -> Option<SQLType> {
     if zero_padded(value.bytes) {
         return None;
     }
+    let out_of_range = limits::beyond_numeric(value.bytes);
     let value = value.text();
     if let Ok(real) = value.parse::<f32>() {
         if real.is_normal() {
             return Some(SQLType {
                 name: SQLTypeName::Float,
                 size: 24,
+                out_of_range,
                 ..Default::default()
             });
         }
@@ -326,11 +450,13 @@ fn check_float(mut value: ByteText, _subindex: usize) -> Option<SQLType> {
     if zero_padded(value.bytes) {
         return None;
     }
+    let out_of_range = limits::beyond_numeric(value.bytes);
     let value = value.text();
-    if value.parse::<f64>().is_ok() {
+    if value.parse::<f64>().is_ok_and(limits::float_fits) {
         Some(SQLType {
             name: SQLTypeName::Float,
             size: 53,
+            out_of_range,
             ..Default::default()
         })
     } else {
@@ -338,20 +464,89 @@ fn check_float(mut value: ByteText, _subindex: usize) -> Option<SQLType> {
     }
 }
 
-fn check_date(mut value: ByteText, subindex: usize) -> Option<SQLType> {
+fn parse_temporal(text: &str, form: &[FormatItem]) -> Option<Parsed> {
+    let mut parsed = Parsed::new();
+    let remaining = parsed.parse_items(text.as_bytes(), form).ok()?;
+    if !remaining.is_empty() {
+        return None;
+    }
+    if parsed.year().is_none() {
+        if let Some(last_two) = parsed.year_last_two() {
+            let mut year = TWO_DIGIT_YEAR_CUTOFF / 100 * 100 + i32::from(last_two);
+            if year > TWO_DIGIT_YEAR_CUTOFF {
+                year -= 100;
+            }
+            parsed.set_year(year)?;
+        }
+    }
+    Some(parsed)
+}
+
+pub(crate) fn parse_date(text: &str, subindex: usize) -> Option<(usize, Date)> {
+    let subindex = subindex.min(timeformats::DATE_FORMATS.len());
     for i in (subindex..timeformats::DATE_FORMATS.len()).chain(0..subindex) {
         let form = timeformats::DATE_FORMATS[i];
-        if Date::parse(value.text(), form).is_ok() {
-            return Some(SQLType {
-                name: SQLTypeName::Date,
-                subindex,
-                ..Default::default()
-            });
+        if let Some(parsed) = parse_temporal(text, form)
+            .and_then(|p| Date::try_from(p).ok())
+            .filter(|&date| limits::date_fits(date))
+        {
+            return Some((i, parsed));
+        }
+    }
+    None
+}
+
+pub(crate) fn parse_time(text: &str, subindex: usize) -> Option<(usize, Time)> {
+    let subindex = subindex.min(timeformats::TIME_FORMATS.len());
+    for i in (subindex..timeformats::TIME_FORMATS.len()).chain(0..subindex) {
+        let form = timeformats::TIME_FORMATS[i];
+        if let Some(parsed) = parse_temporal(text, form).and_then(|p| Time::try_from(p).ok()) {
+            return Some((i, parsed));
+        }
+    }
+    None
+}
+
+pub(crate) fn parse_datetime(text: &str, subindex: usize) -> Option<(usize, PrimitiveDateTime)> {
+    let subindex = subindex.min(timeformats::DATETIME_FORMATS.len());
+    for i in (subindex..timeformats::DATETIME_FORMATS.len()).chain(0..subindex) {
+        let form = timeformats::DATETIME_FORMATS[i];
+        if let Some(parsed) = parse_temporal(text, form)
+            .and_then(|p| PrimitiveDateTime::try_from(p).ok())
+            .filter(|datetime| limits::date_fits(datetime.date()))
+        {
+            return Some((i, parsed));
+        }
+    }
+    None
+}
+
+pub(crate) fn parse_datetimeoffset(text: &str, subindex: usize) -> Option<(usize, OffsetDateTime)> {
+    let subindex = subindex.min(timeformats::DATETIMEOFFSET_FORMATS.len());
+    for i in (subindex..timeformats::DATETIMEOFFSET_FORMATS.len()).chain(0..subindex) {
+        let form = timeformats::DATETIMEOFFSET_FORMATS[i];
+        if let Some(parsed) = parse_temporal(text, form)
+            .or_else(|| parse_temporal(&format!("{}+00:00", text), form))
+            .and_then(|p| OffsetDateTime::try_from(p).ok())
+            .filter(|datetime| {
+                limits::date_fits(datetime.date()) && limits::offset_fits(datetime.offset())
+            })
+        {
+            return Some((i, parsed));
         }
     }
     None
 }
 
+fn check_date(mut value: ByteText, subindex: usize) -> Option<SQLType> {
+    let (subindex, _) = parse_date(value.text(), subindex)?;
+    Some(SQLType {
+        name: SQLTypeName::Date,
+        subindex,
+        ..Default::default()
+    })
+}
+
 fn time_precision(nanoseconds: u32) -> usize {
     if nanoseconds == 0 {
         0
@@ -367,51 +562,87 @@ fn check_time(mut value: ByteText, subindex: usize) -> Option<SQLType> {
     if value.parse::<u8>().is_ok() {
         return None;
     }
-    for i in (subindex..timeformats::TIME_FORMATS.len()).chain(0..subindex) {
-        let form = timeformats::TIME_FORMATS[i];
-        if let Ok(parsed) = Time::parse(value, form) {
-            return Some(SQLType {
-                name: SQLTypeName::Time,
-                subindex,
-                size: time_precision(parsed.nanosecond()),
-                ..Default::default()
-            });
-        }
-    }
-    None
+    let (subindex, parsed) = parse_time(value, subindex)?;
+    Some(SQLType {
+        name: SQLTypeName::Time,
+        subindex,
+        size: time_precision(parsed.nanosecond()),
+        ..Default::default()
+    })
 }
 
 fn check_datetimeoffset(mut value: ByteText, subindex: usize) -> Option<SQLType> {
-    let value = value.text();
-    for i in (subindex..timeformats::DATETIMEOFFSET_FORMATS.len()).chain(0..subindex) {
-        let form = timeformats::DATETIMEOFFSET_FORMATS[i];
-        if let Ok(parsed) = OffsetDateTime::parse(value, form)
-            .or_else(|_| OffsetDateTime::parse(&format!("{}+00:00", &value), form))
-        {
-            return Some(SQLType {
-                name: SQLTypeName::Datetimeoffset,
-                subindex,
-                size: time_precision(parsed.nanosecond()),
-                ..Default::default()
-            });
-        }
-    }
-    None
+    let (subindex, parsed) = parse_datetimeoffset(value.text(), subindex)?;
+    Some(SQLType {
+        name: SQLTypeName::Datetimeoffset,
+        subindex,
+        size: time_precision(parsed.nanosecond()),
+        ..Default::default()
+    })
 }
 
 fn check_datetime(mut value: ByteText, subindex: usize) -> Option<SQLType> {
-    for i in (subindex..timeformats::DATETIME_FORMATS.len()).chain(0..subindex) {
-        let form = timeformats::DATETIME_FORMATS[i];
-        if let Ok(parsed) = PrimitiveDateTime::parse(value.text(), form) {
-            return Some(SQLType {
-                name: SQLTypeName::Datetime2,
-                subindex,
-                size: time_precision(parsed.nanosecond()),
-                ..Default::default()
-            });
-        }
+    let (subindex, parsed) = parse_datetime(value.text(), subindex)?;
+    Some(SQLType {
+        name: SQLTypeName::Datetime2,
+        subindex,
+        size: time_precision(parsed.nanosecond()),
+        ..Default::default()
+    })
+}
+
+fn check_duration(value: ByteText, _subindex: usize) -> Option<SQLType> {
+    duration::check(value.bytes)
+}
+
+/// A JSON object or array
+fn check_json(value: ByteText, _subindex: usize) -> Option<SQLType> {
+    let trimmed = trim(value.bytes);
+    if !matches!(trimmed.first(), Some(b'{' | b'[')) {
+        return None;
     }
-    None
+    serde_json::from_slice::<serde::de::IgnoredAny>(trimmed).ok()?;
+    Some(SQLType {
+        name: SQLTypeName::Json,
+        ..Default::default()
+    })
+}
+
+/// A well-formed XML document or fragment
+fn check_xml(value: ByteText, _subindex: usize) -> Option<SQLType> {
+    let text = from_utf8(trim(value.bytes)).ok()?;
+    if !text.starts_with('<') || !text.ends_with('>') {
+        return None;
+    }
+    let parsed = if text.starts_with("<?xml") {
+        roxmltree::Document::parse(text).is_ok()
+    } else {
+        // a fragment may have several top-level elements
+        roxmltree::Document::parse(&format!("<fragment>{}</fragment>", text)).is_ok()
+    };
+    if !parsed {
+        return None;
+    }
+    Some(SQLType {
+        name: SQLTypeName::Xml,
+        ..Default::default()
+    })
+}
+
+fn check_geography(value: ByteText, _subindex: usize) -> Option<SQLType> {
+    geo::check_geography(value.bytes)
+}
+
+fn check_geometry(value: ByteText, _subindex: usize) -> Option<SQLType> {
+    geo::check_geometry(value.bytes)
+}
+
+fn check_binary(value: ByteText, subindex: usize) -> Option<SQLType> {
+    binary::check(value.bytes, subindex, SQLTypeName::Binary)
+}
+
+fn check_varbinary(value: ByteText, subindex: usize) -> Option<SQLType> {
+    binary::check(value.bytes, subindex, SQLTypeName::Varbinary)
 }
 
 fn check_char(value: ByteText, _subindex: usize) -> Option<SQLType> {
@@ -452,6 +683,88 @@ fn check_varcharmax(value: ByteText, _subindex: usize) -> Option<SQLType> {
     }
 }
 
+/// Rewrite a date or time value as ISO 8601
+pub fn iso8601(value: &[u8], sqltype: &SQLType) -> Option<String> {
+    let text = from_utf8(value).ok()?;
+    if text.is_empty() {
+        return None;
+    }
+    if sqltype.duration {
+        return None;
+    }
+    let precision = sqltype.size;
+    match sqltype.name {
+        SQLTypeName::Date => {
+            let (_, date) = parse_date(text, sqltype.subindex)?;
+            Some(iso8601_date(date))
+        }
+        SQLTypeName::Time => {
+            let (_, time) = parse_time(text, sqltype.subindex)?;
+            Some(iso8601_time(time, precision))
+        }
+        SQLTypeName::Datetime2 => {
+            let datetime = if let Some(unit) = sqltype.epoch {
+                unit.to_datetime(atoi::<i64>(trim(value))?)?
+            } else {
+                parse_datetime(text, sqltype.subindex)
+                    .map(|(_, datetime)| datetime)
+                    .or_else(|| parse_date(text, 0).map(|(_, date)| date.midnight()))?
+            };
+            Some(format!(
+                "{}T{}",
+                iso8601_date(datetime.date()),
+                iso8601_time(datetime.time(), precision)
+            ))
+        }
+        SQLTypeName::Datetimeoffset => {
+            let datetime = parse_datetimeoffset(text, sqltype.subindex)
+                .map(|(_, datetime)| datetime)
+                .or_else(|| parse_date(text, 0).map(|(_, date)| date.midnight().assume_utc()))?;
+            Some(format!(
+                "{}T{}{}",
+                iso8601_date(datetime.date()),
+                iso8601_time(datetime.time(), precision),
+                iso8601_offset(datetime.offset())
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn iso8601_date(date: Date) -> String {
+    format!(
+        "{:04}-{:02}-{:02}",
+        date.year(),
+        u8::from(date.month()),
+        date.day()
+    )
+}
+
+fn iso8601_time(time: Time, precision: usize) -> String {
+    let mut iso = format!(
+        "{:02}:{:02}:{:02}",
+        time.hour(),
+        time.minute(),
+        time.second()
+    );
+    if precision > 0 {
+        let nanos = format!("{:09}", time.nanosecond());
+        iso.push('.');
+        iso.push_str(&nanos[..precision.min(7)]);
+    }
+    iso
+}
+
+fn iso8601_offset(offset: UtcOffset) -> String {
+    let (hours, minutes, _) = offset.as_hms();
+    format!(
+        "{}{:02}:{:02}",
+        if offset.is_negative() { '-' } else { '+' },
+        hours.unsigned_abs(),
+        minutes.unsigned_abs()
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,140 @@
+// Copyright 2023 Jonathan Bowman
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::{trim, SQLType, SQLTypeName};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+/// Shortest bare hex read as binary, the length of an MD5 hash
+const MIN_BARE_HEX: usize = 32;
+
+/// Shortest base64 read as binary
+const MIN_BASE64: usize = 16;
+
+/// Most bytes in a BINARY or VARBINARY short of VARBINARY(MAX)
+const MAX_BYTES: usize = 8000;
+
+/// How binary data is written as text, kept in a column's `subindex`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Encoding {
+    /// hex digits after `0x`
+    PrefixedHex,
+    /// hex digits alone, as hashes are usually written
+    Hex,
+    Base64,
+}
+
+impl Encoding {
+    const ALL: [Encoding; 3] = [Encoding::PrefixedHex, Encoding::Hex, Encoding::Base64];
+
+    pub fn from_subindex(subindex: usize) -> Option<Encoding> {
+        Encoding::ALL.get(subindex).copied()
+    }
+
+    fn subindex(self) -> usize {
+        self as usize
+    }
+
+    /// Number of bytes the value decodes to, if it is written this way
+    fn decoded_length(self, value: &[u8]) -> Option<usize> {
+        match self {
+            Encoding::PrefixedHex => {
+                let digits = value
+                    .strip_prefix(b"0x")
+                    .or_else(|| value.strip_prefix(b"0X"))?;
+                (!digits.is_empty()
+                    && digits.len().is_multiple_of(2)
+                    && digits.iter().all(u8::is_ascii_hexdigit))
+                .then_some(digits.len() / 2)
+            }
+            Encoding::Hex => (value.len() >= MIN_BARE_HEX
+                && value.len().is_multiple_of(2)
+                && value.iter().all(u8::is_ascii_hexdigit)
+                && value.iter().any(u8::is_ascii_alphabetic))
+            .then_some(value.len() / 2),
+            Encoding::Base64 => {
+                // padding or a symbol, which an alphanumeric code never has
+                let marked =
+                    value.ends_with(b"=") || value.iter().any(|c| matches!(c, b'+' | b'/'));
+                if value.len() < MIN_BASE64 || !value.len().is_multiple_of(4) || !marked {
+                    return None;
+                }
+                STANDARD.decode(value).ok().map(|bytes| bytes.len())
+            }
+        }
+    }
+
+    fn decode(self, value: &[u8]) -> Option<Vec<u8>> {
+        match self {
+            Encoding::PrefixedHex => hex_decode(&value[2..]),
+            Encoding::Hex => hex_decode(value),
+            Encoding::Base64 => STANDARD.decode(value).ok(),
+        }
+    }
+}
+
+fn hex_decode(digits: &[u8]) -> Option<Vec<u8>> {
+    digits
+        .chunks(2)
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok())
+        .collect()
+}
+
+/// The encoding and decoded length of a binary value
+pub fn detect(value: &[u8], subindex: usize) -> Option<(Encoding, usize)> {
+    let value = trim(value);
+    let subindex = subindex.min(Encoding::ALL.len());
+    (subindex..Encoding::ALL.len())
+        .chain(0..subindex)
+        .map(|i| Encoding::ALL[i])
+        .find_map(|encoding| Some((encoding, encoding.decoded_length(value)?)))
+        .filter(|&(_, length)| length <= MAX_BYTES)
+}
+
+pub(super) fn check(value: &[u8], subindex: usize, name: SQLTypeName) -> Option<SQLType> {
+    let (encoding, size) = detect(value, subindex)?;
+    Some(SQLType {
+        name,
+        size: size.max(1),
+        subindex: encoding.subindex(),
+        ..Default::default()
+    })
+}
+
+fn decode(value: &[u8], sqltype: &SQLType) -> Option<Vec<u8>> {
+    if !matches!(sqltype.name, SQLTypeName::Binary | SQLTypeName::Varbinary) {
+        return None;
+    }
+    let value = trim(value);
+    if value.is_empty() {
+        return None;
+    }
+    Encoding::from_subindex(sqltype.subindex)?.decode(value)
+}
+
+/// The bytes a binary value stands for
+pub fn to_bytes(value: &[u8], sqltype: &SQLType) -> Option<Vec<u8>> {
+    decode(value, sqltype)
+}
+
+/// Rewrite a binary value as bare hex digits
+pub fn to_hex(value: &[u8], sqltype: &SQLType) -> Option<String> {
+    let bytes = decode(value, sqltype)?;
+    Some(bytes.iter().map(|byte| format!("{:02X}", byte)).collect())
+}
+
+/// Rewrite a binary value in the `\x` hex form
+pub fn to_bytea(value: &[u8], sqltype: &SQLType) -> Option<String> {
+    let bytes = decode(value, sqltype)?;
+    let digits: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    Some(format!("\\x{}", digits))
+}
+
+/// Rewrite a binary value as base64
+pub fn to_base64(value: &[u8], sqltype: &SQLType) -> Option<String> {
+    decode(value, sqltype).map(|bytes| STANDARD.encode(bytes))
+}
@@ -0,0 +1,249 @@
+// Copyright 2023 Jonathan Bowman
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::{trim, SQLType, SQLTypeName};
+use anyhow::bail;
+use serde::Deserialize;
+use simdutf8::basic::from_utf8;
+use std::str::FromStr;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Most fractional second digits SQL Server keeps in a TIME
+const MAX_FRACTION: usize = 7;
+
+/// A length of time, in whole seconds and the digits after the point
+#[derive(Debug, PartialEq)]
+pub struct Duration {
+    pub seconds: u64,
+    pub fraction: String,
+}
+
+/// How a column of durations is stored
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DurationPolicy {
+    /// as a count of seconds, BIGINT or NUMERIC if there are fractions
+    #[default]
+    Seconds,
+    /// as a TIME, if every duration is under 24 hours, else as seconds
+    Time,
+}
+
+impl FromStr for DurationPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &s.to_ascii_lowercase()[..] {
+            "seconds" => Ok(DurationPolicy::Seconds),
+            "time" => Ok(DurationPolicy::Time),
+            _ => bail!("Unknown duration policy {:?}, expected seconds or time", s),
+        }
+    }
+}
+
+impl DurationPolicy {
+    /// Give a column of durations the type the policy stores them as
+    pub fn apply(self, sqltype: &mut SQLType) {
+        if sqltype.name != SQLTypeName::Duration {
+            return;
+        }
+        let fraction = sqltype.size;
+        let whole_days = sqltype.subindex > 0;
+        sqltype.duration = true;
+        sqltype.subindex = 0;
+        if self == DurationPolicy::Time && !whole_days {
+            sqltype.name = SQLTypeName::Time;
+            sqltype.scale = 0;
+        } else if fraction > 0 {
+            sqltype.name = SQLTypeName::Numeric;
+            sqltype.size = 18;
+            sqltype.scale = fraction;
+        } else {
+            sqltype.name = SQLTypeName::Bigint;
+            sqltype.size = 0;
+            sqltype.scale = 0;
+        }
+    }
+}
+
+/// Leading digits of a string, and what follows them
+fn digits(text: &str) -> (&str, &str) {
+    let end = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    text.split_at(end)
+}
+
+fn number(text: &str) -> Option<u64> {
+    if text.is_empty() || !text.bytes().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+/// Seconds with an optional fraction, as in `05` or `05.250`
+fn seconds(text: &str) -> Option<(u64, String)> {
+    let (whole, fraction) = text.split_once('.').unwrap_or((text, ""));
+    if text.contains('.') && (fraction.is_empty() || !fraction.bytes().all(|c| c.is_ascii_digit()))
+    {
+        return None;
+    }
+    Some((number(whole)?, fraction.to_string()))
+}
+
+/// `H:MM` or `H:MM:SS[.fff]`, with any number of hours
+fn clock(text: &str) -> Option<Duration> {
+    let mut parts = text.split(':');
+    let hours = number(parts.next()?)?;
+    let minutes = parts.next()?;
+    let (seconds, fraction) = parts.next().map_or(Some((0, String::new())), seconds)?;
+    if parts.next().is_some() || minutes.len() != 2 {
+        return None;
+    }
+    let minutes = number(minutes)?;
+    if minutes >= 60 || seconds >= 60 {
+        return None;
+    }
+    Some(Duration {
+        seconds: hours
+            .checked_mul(3600)?
+            .checked_add(minutes * 60 + seconds)?,
+        fraction,
+    })
+}
+
+/// `N days`, `N day HH:MM`, `N days, HH:MM:SS` and the like
+fn days(text: &str) -> Option<Duration> {
+    let (count, rest) = digits(text);
+    let count = number(count)?;
+    let rest = rest.trim_start();
+    let rest = rest
+        .strip_prefix("days")
+        .or_else(|| rest.strip_prefix("day"))?;
+    let rest = rest.trim_start_matches(',').trim();
+    let mut duration = if rest.is_empty() {
+        Duration {
+            seconds: 0,
+            fraction: String::new(),
+        }
+    } else {
+        clock(rest)?
+    };
+    if duration.seconds >= SECONDS_PER_DAY {
+        return None;
+    }
+    duration.seconds = count
+        .checked_mul(SECONDS_PER_DAY)?
+        .checked_add(duration.seconds)?;
+    Some(duration)
+}
+
+/// ISO 8601 durations in weeks, days, hours, minutes and seconds
+fn iso8601(text: &str) -> Option<Duration> {
+    let mut rest = text.strip_prefix('P')?;
+    let mut seconds = 0u64;
+    let mut fraction = String::new();
+    let mut time = false;
+    let mut components = 0;
+    let mut units = "WD";
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('T') {
+            if time {
+                return None;
+            }
+            time = true;
+            units = "HMS";
+            rest = after;
+            continue;
+        }
+        let end = rest.find(|c: char| !(c.is_ascii_digit() || c == '.'))?;
+        let (value, after) = rest.split_at(end);
+        let unit = after.chars().next()?;
+        let position = units.find(unit)?;
+        units = &units[position + 1..];
+        let per = match (time, unit) {
+            (false, 'W') => 7 * SECONDS_PER_DAY,
+            (false, 'D') => SECONDS_PER_DAY,
+            (true, 'H') => 3600,
+            (true, 'M') => 60,
+            (true, 'S') => 1,
+            _ => return None,
+        };
+        let whole = if unit == 'S' {
+            let (whole, digits) = self::seconds(value)?;
+            fraction = digits;
+            whole
+        } else {
+            number(value)?
+        };
+        seconds = seconds.checked_add(whole.checked_mul(per)?)?;
+        components += 1;
+        rest = &after[1..];
+    }
+    // a bare `P`, or a `T` with nothing after it, is not a duration
+    if components == 0 || (time && units == "HMS") {
+        return None;
+    }
+    Some(Duration { seconds, fraction })
+}
+
+/// A duration in any of the forms recognized
+pub fn parse(value: &[u8]) -> Option<Duration> {
+    let text = from_utf8(trim(value)).ok()?;
+    let duration = if text.starts_with('P') {
+        iso8601(text)
+    } else if text.contains("day") {
+        days(text)
+    } else {
+        clock(text)
+    }?;
+    (duration.fraction.len() <= MAX_FRACTION).then_some(duration)
+}
+
+/// The type of a duration value
+pub(super) fn check(value: &[u8]) -> Option<SQLType> {
+    let duration = parse(value)?;
+    Some(SQLType {
+        name: SQLTypeName::Duration,
+        size: duration.fraction.len(),
+        subindex: usize::from(duration.seconds >= SECONDS_PER_DAY),
+        ..Default::default()
+    })
+}
+
+/// Rewrite a duration as the column stores it
+pub fn convert(value: &[u8], sqltype: &SQLType) -> Option<String> {
+    if !sqltype.duration {
+        return None;
+    }
+    let duration = parse(value)?;
+    let (digits, whole) = match sqltype.name {
+        SQLTypeName::Time => {
+            let seconds = duration.seconds;
+            (
+                sqltype.size,
+                format!(
+                    "{:02}:{:02}:{:02}",
+                    seconds / 3600,
+                    seconds / 60 % 60,
+                    seconds % 60
+                ),
+            )
+        }
+        _ => (sqltype.scale, duration.seconds.to_string()),
+    };
+    if digits == 0 {
+        return Some(whole);
+    }
+    Some(format!(
+        "{}.{:0<width$}",
+        whole,
+        duration.fraction,
+        width = digits
+    ))
+}
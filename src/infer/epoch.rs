@@ -0,0 +1,123 @@
+// Copyright 2023 Jonathan Bowman
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::{trim, SQLType, SQLTypeName};
+use atoi::atoi;
+use serde::{Deserialize, Serialize};
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+/// Column names ending in one of these words hint at epoch timestamps
+const NAME_HINTS: [&str; 5] = ["ts", "epoch", "timestamp", "unixtime", "unix"];
+
+/// Without a name hint, values must fall between 1990-01-01 and 2100-01-01
+const EARLIEST: i64 = 631_152_000;
+const LATEST: i64 = 4_102_444_800;
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum EpochUnit {
+    Seconds,
+    Milliseconds,
+    Microseconds,
+}
+
+impl EpochUnit {
+    const ALL: [EpochUnit; 3] = [
+        EpochUnit::Seconds,
+        EpochUnit::Milliseconds,
+        EpochUnit::Microseconds,
+    ];
+
+    fn per_second(self) -> i64 {
+        match self {
+            EpochUnit::Seconds => 1,
+            EpochUnit::Milliseconds => 1_000,
+            EpochUnit::Microseconds => 1_000_000,
+        }
+    }
+
+    /// Fractional second digits needed to hold a value of this unit
+    pub fn precision(self) -> usize {
+        match self {
+            EpochUnit::Seconds => 0,
+            EpochUnit::Milliseconds => 3,
+            EpochUnit::Microseconds => 6,
+        }
+    }
+
+    pub fn to_datetime(self, value: i64) -> Option<PrimitiveDateTime> {
+        let nanos = i128::from(value) * i128::from(1_000_000_000 / self.per_second());
+        let datetime = OffsetDateTime::from_unix_timestamp_nanos(nanos).ok()?;
+        Some(PrimitiveDateTime::new(datetime.date(), datetime.time()))
+    }
+}
+
+/// Smallest and largest integers seen in a column
+#[derive(Clone, Debug)]
+pub struct IntegerRange {
+    min: i64,
+    max: i64,
+    integral: bool,
+}
+
+impl Default for IntegerRange {
+    fn default() -> Self {
+        IntegerRange {
+            min: i64::MAX,
+            max: i64::MIN,
+            integral: true,
+        }
+    }
+}
+
+impl IntegerRange {
+    pub fn update(&mut self, value: &[u8]) {
+        let value = trim(value);
+        if value.is_empty() || !self.integral {
+            return;
+        }
+        if let Some(number) = atoi::<i64>(value).filter(|_| {
+            let digits = value.strip_prefix(b"-").unwrap_or(value);
+            digits.iter().all(u8::is_ascii_digit)
+        }) {
+            self.min = self.min.min(number);
+            self.max = self.max.max(number);
+        } else {
+            self.integral = false;
+        }
+    }
+}
+
+fn name_hint(column: &str) -> bool {
+    let column = column.to_ascii_lowercase();
+    let last_word = column.rsplit('_').next().unwrap_or(&column);
+    NAME_HINTS.contains(&last_word)
+}
+
+/// Decide whether an integer column holds Unix epoch timestamps
+pub fn detect(column: &str, sqltype: &SQLType, range: &IntegerRange) -> Option<EpochUnit> {
+    if !matches!(sqltype.name, SQLTypeName::Int | SQLTypeName::Bigint)
+        || !range.integral
+        || range.min > range.max
+        || range.min < 0
+    {
+        return None;
+    }
+    let hinted = name_hint(column);
+    let unit = EpochUnit::ALL
+        .into_iter()
+        .find(|unit| range.max < LATEST * unit.per_second())?;
+    let earliest = if hinted {
+        0
+    } else {
+        EARLIEST * unit.per_second()
+    };
+    if range.min >= earliest {
+        Some(unit)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,323 @@
+// Copyright 2023 Jonathan Bowman
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::{trim, SQLType, SQLTypeName};
+use crate::dialect::Dialect;
+use simdutf8::basic::from_utf8;
+
+/// Spatial reference of longitude and latitude on WGS 84
+pub const WGS84: u32 = 4326;
+
+const LATITUDE_HINTS: [&str; 2] = ["latitude", "lat"];
+const LONGITUDE_HINTS: [&str; 4] = ["longitude", "long", "lng", "lon"];
+
+fn latitude_fits(latitude: f64) -> bool {
+    (-90.0..=90.0).contains(&latitude)
+}
+
+fn longitude_fits(longitude: f64) -> bool {
+    (-180.0..=180.0).contains(&longitude)
+}
+
+/// A reader of Well-Known Text
+struct Wkt<'a> {
+    text: &'a [u8],
+    pos: usize,
+    geographic: bool,
+}
+
+impl<'a> Wkt<'a> {
+    fn skip_space(&mut self) {
+        while self.text.get(self.pos).is_some_and(u8::is_ascii_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, c: u8) -> bool {
+        self.skip_space();
+        if self.text.get(self.pos) == Some(&c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: u8) -> Option<()> {
+        self.eat(c).then_some(())
+    }
+
+    fn word(&mut self) -> &'a [u8] {
+        self.skip_space();
+        let start = self.pos;
+        while self.text.get(self.pos).is_some_and(u8::is_ascii_alphabetic) {
+            self.pos += 1;
+        }
+        &self.text[start..self.pos]
+    }
+
+    fn number(&mut self) -> Option<f64> {
+        self.skip_space();
+        let start = self.pos;
+        while self
+            .text
+            .get(self.pos)
+            .is_some_and(|c| matches!(c, b'0'..=b'9' | b'.' | b'-' | b'+' | b'e' | b'E'))
+        {
+            self.pos += 1;
+        }
+        from_utf8(&self.text[start..self.pos]).ok()?.parse().ok()
+    }
+
+    /// Two to four numbers: x and y, then optional z and m
+    fn coordinate(&mut self) -> Option<()> {
+        let x = self.number()?;
+        let y = self.number()?;
+        if !x.is_finite() || !y.is_finite() {
+            return None;
+        }
+        for _ in 0..2 {
+            self.skip_space();
+            if !self
+                .text
+                .get(self.pos)
+                .is_some_and(|c| c.is_ascii_digit() || matches!(c, b'-' | b'+' | b'.'))
+            {
+                break;
+            }
+            self.number()?;
+        }
+        self.geographic &= longitude_fits(x) && latitude_fits(y);
+        Some(())
+    }
+
+    /// A parenthesized, comma-separated list of whatever `item` reads
+    fn list(&mut self, item: fn(&mut Self) -> Option<()>) -> Option<()> {
+        self.expect(b'(')?;
+        item(self)?;
+        while self.eat(b',') {
+            item(self)?;
+        }
+        self.expect(b')')
+    }
+
+    fn coordinates(&mut self) -> Option<()> {
+        self.list(Self::coordinate)
+    }
+
+    fn rings(&mut self) -> Option<()> {
+        self.list(Self::coordinates)
+    }
+
+    fn polygons(&mut self) -> Option<()> {
+        self.list(Self::rings)
+    }
+
+    /// A point of a MULTIPOINT, which may or may not be parenthesized
+    fn member_point(&mut self) -> Option<()> {
+        if self.eat(b'(') {
+            self.coordinate()?;
+            self.expect(b')')
+        } else {
+            self.coordinate()
+        }
+    }
+
+    fn geometry(&mut self) -> Option<()> {
+        let kind = self.word().to_ascii_uppercase();
+        let mut next = self.word().to_ascii_uppercase();
+        if matches!(&next[..], b"Z" | b"M" | b"ZM") {
+            next = self.word().to_ascii_uppercase();
+        }
+        if next == b"EMPTY" {
+            return matches!(
+                &kind[..],
+                b"POINT"
+                    | b"LINESTRING"
+                    | b"POLYGON"
+                    | b"MULTIPOINT"
+                    | b"MULTILINESTRING"
+                    | b"MULTIPOLYGON"
+                    | b"GEOMETRYCOLLECTION"
+            )
+            .then_some(());
+        }
+        if !next.is_empty() {
+            return None;
+        }
+        match &kind[..] {
+            b"POINT" => self.list(Self::coordinate),
+            b"LINESTRING" => self.coordinates(),
+            b"POLYGON" | b"MULTILINESTRING" => self.rings(),
+            b"MULTIPOLYGON" => self.polygons(),
+            b"MULTIPOINT" => self.list(Self::member_point),
+            b"GEOMETRYCOLLECTION" => self.list(Self::geometry),
+            _ => None,
+        }
+    }
+}
+
+/// Whether a value is Well-Known Text with valid coordinates
+pub fn parse_wkt(value: &[u8]) -> Option<bool> {
+    let text = trim(value);
+    if !text.first().is_some_and(u8::is_ascii_alphabetic) {
+        return None;
+    }
+    let mut wkt = Wkt {
+        text,
+        pos: 0,
+        geographic: true,
+    };
+    wkt.geometry()?;
+    wkt.skip_space();
+    (wkt.pos == text.len()).then_some(wkt.geographic)
+}
+
+/// A WKT value whose coordinates all fit longitude and latitude
+pub(super) fn check_geography(value: &[u8]) -> Option<SQLType> {
+    parse_wkt(value)?.then(|| SQLType {
+        name: SQLTypeName::Geography,
+        ..Default::default()
+    })
+}
+
+/// Any WKT value, in a plane without a spatial reference
+pub(super) fn check_geometry(value: &[u8]) -> Option<SQLType> {
+    parse_wkt(value)?;
+    Some(SQLType {
+        name: SQLTypeName::Geometry,
+        ..Default::default()
+    })
+}
+
+/// The expression converting WKT text to a spatial type
+pub fn from_text(column: &str, sqltype: &SQLType) -> Option<String> {
+    match sqltype.name {
+        SQLTypeName::Geography => Some(format!("geography::STGeomFromText({}, {})", column, WGS84)),
+        SQLTypeName::Geometry => Some(format!("geometry::STGeomFromText({}, 0)", column)),
+        _ => None,
+    }
+}
+
+/// A latitude column and the longitude column sharing its name
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoordinatePair {
+    pub latitude: usize,
+    pub longitude: usize,
+    /// what the two names have in common, such as `pickup_`
+    pub prefix: String,
+}
+
+impl CoordinatePair {
+    /// Name of the computed column holding the point
+    pub fn name(&self) -> String {
+        format!("{}point", self.prefix)
+    }
+
+    /// Definition of a computed geography column built from the pair
+    pub fn computed_column(&self, columns: &[String], dialect: Dialect) -> String {
+        let name = dialect.identifier(&self.name());
+        let (latitude, longitude) = (&columns[self.latitude], &columns[self.longitude]);
+        match dialect {
+            Dialect::SqlServer => format!(
+                "{} AS geography::Point({}, {}, {})",
+                name, latitude, longitude, WGS84
+            ),
+            Dialect::Postgres => format!(
+                "{} geography GENERATED ALWAYS AS \
+                (ST_SetSRID(ST_MakePoint({}, {}), {})::geography) STORED",
+                name, longitude, latitude, WGS84
+            ),
+            // without SpatiaLite, the point is kept as WKT
+            Dialect::Sqlite => format!(
+                "{} TEXT GENERATED ALWAYS AS ('POINT(' || {} || ' ' || {} || ')')",
+                name, longitude, latitude
+            ),
+            Dialect::Mysql => format!(
+                "{name} POINT SRID {WGS84} GENERATED ALWAYS AS (ST_PointFromText(\
+                CONCAT('POINT(', {longitude}, ' ', {latitude}, ')'), {WGS84}, \
+                'axis-order=long-lat')) STORED"
+            ),
+            // kept as WKT, like the spatial columns
+            Dialect::Oracle { .. } => format!(
+                "{} VARCHAR2(100) GENERATED ALWAYS AS ('POINT(' || {} || ' ' || {} || ')') VIRTUAL",
+                name, longitude, latitude
+            ),
+        }
+    }
+}
+
+/// The part of a column name before a coordinate hint, if it ends in one
+fn hinted_prefix(column: &str, hints: &[&str]) -> Option<String> {
+    let column = column.to_ascii_lowercase();
+    hints.iter().find_map(|hint| {
+        let prefix = column.strip_suffix(hint)?;
+        (prefix.is_empty() || prefix.ends_with('_')).then(|| prefix.to_string())
+    })
+}
+
+fn numeric(sqltype: &SQLType) -> bool {
+    matches!(
+        sqltype.name,
+        SQLTypeName::Tinyint
+            | SQLTypeName::Smallint
+            | SQLTypeName::Int
+            | SQLTypeName::Numeric
+            | SQLTypeName::Float
+    )
+}
+
+/// Numeric columns whose names mark them as latitude and longitude
+pub fn coordinate_pairs(columns: &[String], sqltypes: &[SQLType]) -> Vec<CoordinatePair> {
+    let mut pairs = Vec::new();
+    for (latitude, column) in columns.iter().enumerate() {
+        let Some(prefix) = hinted_prefix(column, &LATITUDE_HINTS) else {
+            continue;
+        };
+        let longitude = columns
+            .iter()
+            .position(|other| hinted_prefix(other, &LONGITUDE_HINTS).as_ref() == Some(&prefix));
+        if let Some(longitude) = longitude {
+            if numeric(&sqltypes[latitude]) && numeric(&sqltypes[longitude]) {
+                pairs.push(CoordinatePair {
+                    latitude,
+                    longitude,
+                    prefix,
+                });
+            }
+        }
+    }
+    pairs
+}
+
+/// Whether every value seen in a pair of columns is in range
+#[derive(Clone, Debug)]
+pub struct Bounds {
+    pub fits: bool,
+}
+
+impl Default for Bounds {
+    fn default() -> Self {
+        Bounds { fits: true }
+    }
+}
+
+impl Bounds {
+    pub fn update(&mut self, latitude: &[u8], longitude: &[u8]) {
+        for (value, fits) in [
+            (latitude, latitude_fits as fn(f64) -> bool),
+            (longitude, longitude_fits),
+        ] {
+            let value = trim(value);
+            if value.is_empty() {
+                continue;
+            }
+            let number = from_utf8(value).ok().and_then(|text| text.parse().ok());
+            self.fits &= number.is_some_and(fits);
+        }
+    }
+}
@@ -0,0 +1,102 @@
+// Copyright 2023 Jonathan Bowman
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::{signed, trim, SQLType, SQLTypeName};
+use anyhow::bail;
+use atoi::atoi;
+use std::str::FromStr;
+use time::{Date, UtcOffset};
+
+/// Most digits SQL Server holds in a NUMERIC
+pub const MAX_PRECISION: usize = 38;
+
+/// SQL Server dates run from 0001-01-01 through 9999-12-31
+const MIN_YEAR: i32 = 1;
+const MAX_YEAR: i32 = 9999;
+
+/// SQL Server offsets run from -14:00 through +14:00
+const MAX_OFFSET_SECONDS: i32 = 14 * 60 * 60;
+
+pub fn date_fits(date: Date) -> bool {
+    (MIN_YEAR..=MAX_YEAR).contains(&date.year())
+}
+
+pub fn offset_fits(offset: UtcOffset) -> bool {
+    offset.whole_seconds().abs() <= MAX_OFFSET_SECONDS
+}
+
+/// FLOAT holds neither infinities, NaN, nor subnormal numbers
+pub fn float_fits(number: f64) -> bool {
+    number == 0.0 || number.is_normal()
+}
+
+/// An integer too large for BIGINT
+pub fn beyond_bigint(value: &[u8]) -> bool {
+    let value = trim(value);
+    let digits = signed(value);
+    !digits.is_empty() && digits.iter().all(u8::is_ascii_digit) && atoi::<i64>(value).is_none()
+}
+
+/// A plain decimal number with more digits than NUMERIC holds
+pub fn beyond_numeric(value: &[u8]) -> bool {
+    let value = signed(trim(value));
+    value.iter().filter(|&&c| c == b'.').count() <= 1
+        && value.iter().all(|c| matches!(c, b'.' | b'0'..=b'9'))
+        && value.iter().filter(|c| c.is_ascii_digit()).count() > MAX_PRECISION
+}
+
+/// What to do with numbers beyond what NUMERIC or BIGINT holds exactly
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum RangePolicy {
+    /// take the next type up that holds every value
+    #[default]
+    Widen,
+    /// go straight to FLOAT, even where NUMERIC would do
+    Float,
+    /// keep the digits exactly, as VARCHAR
+    Varchar,
+}
+
+impl FromStr for RangePolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &s.to_ascii_lowercase()[..] {
+            "widen" => Ok(RangePolicy::Widen),
+            "float" => Ok(RangePolicy::Float),
+            "varchar" => Ok(RangePolicy::Varchar),
+            _ => bail!(
+                "Unknown range policy {:?}, expected widen, float or varchar",
+                s
+            ),
+        }
+    }
+}
+
+impl RangePolicy {
+    /// Change the type of a column that went out of range, as the policy says
+    pub fn apply(self, sqltype: &mut SQLType) {
+        if !sqltype.out_of_range {
+            return;
+        }
+        match (self, sqltype.name) {
+            (RangePolicy::Float, SQLTypeName::Numeric) => {
+                sqltype.name = SQLTypeName::Float;
+                sqltype.size = 53;
+                sqltype.scale = 0;
+                sqltype.subindex = 0;
+            }
+            (RangePolicy::Varchar, SQLTypeName::Numeric | SQLTypeName::Float) => {
+                sqltype.name = SQLTypeName::Varchar;
+                sqltype.size = sqltype.byte_length.max(1);
+                sqltype.scale = 0;
+                sqltype.subindex = 0;
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,246 @@
+// Copyright 2023 Jonathan Bowman
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::*;
+use std::sync::OnceLock;
+
+/// One check of the inference ladder
+pub trait TypeCheck: Send + Sync {
+    /// Position in the ladder; lower priorities are tried first
+    fn priority(&self) -> u32;
+
+    /// The type of a single non-empty value, or `None` to pass it on
+    fn check(&self, value: &[u8], subindex: usize) -> Option<SQLType>;
+
+    /// Fold a value's type into a column
+    fn merge(&self, column: &mut SQLType, other: &SQLType) {
+        column.subindex = other.subindex.max(column.subindex);
+        column.size = other.size.max(column.size);
+        column.scale = other.scale.max(column.scale);
+    }
+}
+
+type Check = fn(ByteText, usize) -> Option<SQLType>;
+
+struct Builtin {
+    name: SQLTypeName,
+    priority: u32,
+    check: Check,
+}
+
+const BUILTINS: [(SQLTypeName, Check); 22] = [
+    (SQLTypeName::Bit, check_bit),
+    (SQLTypeName::Tinyint, check_tinyint),
+    (SQLTypeName::Smallint, check_smallint),
+    (SQLTypeName::Int, check_int),
+    (SQLTypeName::Bigint, check_bigint),
+    (SQLTypeName::Numeric, check_decimal),
+    (SQLTypeName::Float, check_real),
+    (SQLTypeName::Float, check_float),
+    (SQLTypeName::Date, check_date),
+    (SQLTypeName::Time, check_time),
+    (SQLTypeName::Datetime2, check_datetime),
+    (SQLTypeName::Datetimeoffset, check_datetimeoffset),
+    (SQLTypeName::Duration, check_duration),
+    (SQLTypeName::Json, check_json),
+    (SQLTypeName::Xml, check_xml),
+    (SQLTypeName::Geography, check_geography),
+    (SQLTypeName::Geometry, check_geometry),
+    (SQLTypeName::Binary, check_binary),
+    (SQLTypeName::Varbinary, check_varbinary),
+    (SQLTypeName::Char, check_char),
+    (SQLTypeName::Varchar, check_varchar),
+    (SQLTypeName::Varcharmax, check_varcharmax),
+];
+
+impl TypeCheck for Builtin {
+    fn priority(&self) -> u32 {
+        self.priority
+    }
+
+    fn check(&self, value: &[u8], subindex: usize) -> Option<SQLType> {
+        (self.check)(ByteText::new(value), subindex)
+    }
+
+    fn merge(&self, column: &mut SQLType, other: &SQLType) {
+        match self.name {
+            // fixed widths that differ make a variable width
+            SQLTypeName::Char | SQLTypeName::Binary if other.size != column.size => {
+                column.name = if self.name == SQLTypeName::Char {
+                    SQLTypeName::Varchar
+                } else {
+                    SQLTypeName::Varbinary
+                };
+                column.size = other.size.max(column.size);
+            }
+            SQLTypeName::Numeric => {
+                column.size = other.size.max(column.size);
+                column.scale = other.scale.max(column.scale);
+                if column.size + column.scale > limits::MAX_PRECISION {
+                    column.name = SQLTypeName::Float;
+                    column.size = 53;
+                    column.scale = 0;
+                    column.subindex = 0;
+                    column.out_of_range = true;
+                }
+            }
+            _ => {
+                column.subindex = other.subindex.max(column.subindex);
+                column.size = other.size.max(column.size);
+                column.scale = other.scale.max(column.scale);
+            }
+        }
+    }
+}
+
+/// The checks a column's values are run through, in order
+pub struct Registry {
+    checks: Vec<Box<dyn TypeCheck>>,
+    /// The type each built-in check produces, `None` for custom checks
+    builtins: Vec<Option<SQLTypeName>>,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        let (checks, builtins) = BUILTINS
+            .iter()
+            .enumerate()
+            .map(|(i, &(name, check))| {
+                let builtin: Box<dyn TypeCheck> = Box::new(Builtin {
+                    name,
+                    priority: 100 * (i as u32 + 1),
+                    check,
+                });
+                (builtin, Some(name))
+            })
+            .unzip();
+        Registry { checks, builtins }
+    }
+}
+
+impl fmt::Debug for Registry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list()
+            .entries(
+                self.checks
+                    .iter()
+                    .zip(&self.builtins)
+                    .map(|(check, builtin)| (check.priority(), builtin)),
+            )
+            .finish()
+    }
+}
+
+impl Registry {
+    /// The built-in checks alone, shared
+    pub fn builtin() -> &'static Registry {
+        static BUILTIN: OnceLock<Registry> = OnceLock::new();
+        BUILTIN.get_or_init(Registry::default)
+    }
+
+    /// Add a check, after any others of the same priority
+    pub fn insert(&mut self, check: Box<dyn TypeCheck>) {
+        let at = self
+            .checks
+            .partition_point(|existing| existing.priority() <= check.priority());
+        self.checks.insert(at, check);
+        self.builtins.insert(at, None);
+    }
+
+    /// Position of the widest built-in check for a type
+    pub(crate) fn position(&self, name: SQLTypeName) -> usize {
+        self.builtins
+            .iter()
+            .rposition(|&builtin| builtin == Some(name))
+            .unwrap_or(self.checks.len())
+    }
+
+    /// The type of a single value, trying checks from `index` upward
+    pub fn infer(&self, value: &[u8], index: usize, subindex: usize) -> Option<SQLType> {
+        if value.is_empty() {
+            return Some(SQLType {
+                ..Default::default()
+            });
+        }
+        for (index, check) in self.checks.iter().enumerate().skip(index) {
+            if let Some(mut typesize) = check.check(value, subindex) {
+                typesize.index = index;
+                typesize.byte_length = value.len();
+                return Some(typesize);
+            }
+        }
+        None
+    }
+
+    /// Like [`Registry::infer`], but a number with leading zeros is still a number
+    pub fn infer_number(&self, value: &[u8], index: usize, subindex: usize) -> Option<SQLType> {
+        let Some(unpadded) = unpadded(value) else {
+            return self.infer(value, index, subindex);
+        };
+        match self.infer(&unpadded, index, subindex) {
+            Some(mut found) if found.index <= self.position(SQLTypeName::Float) => {
+                found.byte_length = value.len();
+                Some(found)
+            }
+            _ => self.infer(value, index, subindex),
+        }
+    }
+
+    /// Fold the type of another value into a column
+    pub fn merge(&self, column: &mut SQLType, other: &SQLType) {
+        let seen = column.byte_length > 0;
+        column.byte_length = other.byte_length.max(column.byte_length);
+        column.out_of_range |= other.out_of_range;
+        let varchar = self.position(SQLTypeName::Varchar);
+        let mixed = column.exclusive() != other.exclusive()
+            || (column.exclusive().is_some() && column.subindex != other.subindex);
+        if mixed
+            && seen
+            && other.byte_length > 0
+            && (column.index < varchar || other.index < varchar)
+        {
+            // values that are not all JSON, all XML, all WKT, or all binary in
+            // one encoding are plain text
+            self.text(column, other);
+        } else if column.index == other.index {
+            let name = column.name;
+            if let Some(check) = self.checks.get(column.index) {
+                check.merge(column, other);
+            }
+            if column.name != name {
+                column.index = self.position(column.name);
+            }
+        } else if column.index < other.index {
+            if other.name == SQLTypeName::Char {
+                column.size = other.size.max(column.byte_length);
+            } else if matches!(other.name, SQLTypeName::Varbinary | SQLTypeName::Duration) {
+                // keep the widest value so far, or the precision of a time
+                column.size = other.size.max(column.size);
+            } else {
+                column.size = other.size;
+            }
+            column.name = other.name;
+            column.index = other.index;
+            column.subindex = other.subindex;
+            column.scale = other.scale;
+        }
+    }
+
+    /// Fall back to text wide enough for every value so far
+    fn text(&self, column: &mut SQLType, other: &SQLType) {
+        if column.byte_length > 8000 || other.name == SQLTypeName::Varcharmax {
+            column.name = SQLTypeName::Varcharmax;
+            column.size = 0;
+        } else {
+            column.name = SQLTypeName::Varchar;
+            column.size = column.byte_length;
+        }
+        column.index = self.position(column.name);
+        column.subindex = 0;
+        column.scale = 0;
+    }
+}
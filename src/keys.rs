@@ -0,0 +1,324 @@
+// Copyright 2023 Jonathan Bowman
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::infer::{SQLType, SQLTypeName};
+use anyhow::Result;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+
+/// Default memory allowed for the value sets, in bytes
+pub const DEFAULT_MEMORY_CAP: usize = 256 * 1024 * 1024;
+
+/// Most column combinations checked at any one size
+const MAX_COMBINATIONS: usize = 500;
+
+/// SQL Server will not index a key wider than this
+const MAX_KEY_BYTES: usize = 900;
+
+/// Rough bookkeeping cost of one entry in a set, beyond the key itself
+pub(crate) const ENTRY_OVERHEAD: usize = 48;
+const HASHED_ENTRY: usize = 16;
+
+/// Column combinations that are unique and never blank across a file
+#[derive(Debug, Default)]
+pub struct KeyCandidates {
+    /// Minimal unique combinations, as column indexes
+    pub unique: Vec<Vec<usize>>,
+    /// Some combinations were confirmed by hash rather than by exact value
+    pub approximate: bool,
+    /// Combinations skipped because of the memory cap or combination limit
+    pub unchecked: usize,
+    /// More combinations were skipped than were counted
+    pub unchecked_more: bool,
+}
+
+impl KeyCandidates {
+    /// Pick the best primary key
+    pub fn primary(&self, sqltypes: &[SQLType]) -> Option<&[usize]> {
+        self.unique
+            .iter()
+            .min_by_key(|key| {
+                let integral = key.iter().all(|&i| {
+                    matches!(
+                        sqltypes[i].name,
+                        SQLTypeName::Tinyint
+                            | SQLTypeName::Smallint
+                            | SQLTypeName::Int
+                            | SQLTypeName::Bigint
+                    )
+                });
+                let width: usize = key.iter().map(|&i| sqltypes[i].byte_length).sum();
+                (key.len(), !integral, width, key.to_vec())
+            })
+            .map(Vec::as_slice)
+    }
+}
+
+enum Seen {
+    Exact(HashSet<Vec<u8>>),
+    Hashed(HashSet<u64>),
+}
+
+impl Seen {
+    /// Add a key, returning false if it was already present
+    fn insert(&mut self, key: Vec<u8>) -> bool {
+        match self {
+            Seen::Exact(set) => set.insert(key),
+            Seen::Hashed(set) => set.insert(hash(&key)),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Seen::Exact(set) => set.len(),
+            Seen::Hashed(set) => set.len(),
+        }
+    }
+
+    fn hashed(&self) -> Seen {
+        match self {
+            Seen::Exact(set) => Seen::Hashed(set.iter().map(|key| hash(key)).collect()),
+            Seen::Hashed(set) => Seen::Hashed(set.clone()),
+        }
+    }
+
+    fn cost(&self) -> usize {
+        match self {
+            Seen::Exact(set) => set.iter().map(|key| key.len() + ENTRY_OVERHEAD).sum(),
+            Seen::Hashed(set) => set.len() * HASHED_ENTRY,
+        }
+    }
+}
+
+fn hash(key: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+struct Candidate {
+    columns: Vec<usize>,
+    seen: Option<Seen>,
+    unique: bool,
+    blank: bool,
+}
+
+/// Values of several columns joined so that no two combinations collide
+fn composite(columns: &[usize], row: &csv::ByteRecord) -> Option<Vec<u8>> {
+    let mut key = Vec::new();
+    for &i in columns {
+        let value = row.get(i).unwrap_or_default();
+        if value.is_empty() {
+            return None;
+        }
+        key.extend_from_slice(&(value.len() as u32).to_le_bytes());
+        key.extend_from_slice(value);
+    }
+    Some(key)
+}
+
+/// Check a set of column combinations in one pass over the file
+fn check(
+    csvfile: &PathBuf,
+    field_sep: Option<u8>,
+    row_sep: Option<u8>,
+    combos: Vec<Vec<usize>>,
+    memory_cap: usize,
+    found: &mut KeyCandidates,
+) -> Result<(Vec<Candidate>, usize)> {
+    let mut rdr = crate::csv_reader(csvfile, field_sep, row_sep)?;
+    let mut candidates: Vec<Candidate> = combos
+        .into_iter()
+        .map(|columns| Candidate {
+            columns,
+            seen: Some(Seen::Exact(HashSet::new())),
+            unique: true,
+            blank: false,
+        })
+        .collect();
+    let mut used = 0;
+    let mut hashed = false;
+    let mut rows = 0;
+    let mut row = csv::ByteRecord::new();
+    while rdr.read_byte_record(&mut row)? {
+        rows += 1;
+        for candidate in candidates.iter_mut() {
+            let Some(seen) = &mut candidate.seen else {
+                continue;
+            };
+            let Some(key) = composite(&candidate.columns, &row) else {
+                candidate.blank = true;
+                candidate.unique = false;
+                candidate.seen = None;
+                continue;
+            };
+            let cost = if hashed {
+                HASHED_ENTRY
+            } else {
+                key.len() + ENTRY_OVERHEAD
+            };
+            if seen.insert(key) {
+                used += cost;
+            } else {
+                candidate.unique = false;
+                if candidate.columns.len() > 1 {
+                    candidate.seen = None;
+                }
+            }
+        }
+        if used > memory_cap && !hashed {
+            hashed = true;
+            found.approximate = true;
+            for candidate in candidates.iter_mut() {
+                candidate.seen = candidate.seen.as_ref().map(Seen::hashed);
+            }
+            used = candidates
+                .iter()
+                .filter_map(|c| c.seen.as_ref())
+                .map(Seen::cost)
+                .sum();
+        }
+        if used > memory_cap {
+            // Distinct counts only help pruning, so they go first
+            for candidate in candidates.iter_mut().filter(|c| !c.unique) {
+                candidate.seen = None;
+            }
+            used = candidates
+                .iter()
+                .filter_map(|c| c.seen.as_ref())
+                .map(Seen::cost)
+                .sum();
+            if used > memory_cap {
+                for candidate in candidates.iter_mut().filter(|c| c.seen.is_some()) {
+                    candidate.seen = None;
+                    candidate.unique = false;
+                    found.unchecked += 1;
+                }
+                used = 0;
+            }
+        }
+    }
+    Ok((candidates, rows))
+}
+
+fn eligible(sqltype: &SQLType) -> bool {
+    !matches!(
+        sqltype.name,
+        SQLTypeName::Float
+            | SQLTypeName::Json
+            | SQLTypeName::Xml
+            | SQLTypeName::Geography
+            | SQLTypeName::Geometry
+            | SQLTypeName::Varcharmax
+    ) && sqltype.byte_length <= MAX_KEY_BYTES
+}
+
+/// Every combination of `size` columns, in column order
+struct Combinations<'a> {
+    columns: &'a [usize],
+    /// positions in `columns` of the next combination
+    indexes: Vec<usize>,
+    done: bool,
+}
+
+impl<'a> Combinations<'a> {
+    fn new(columns: &'a [usize], size: usize) -> Self {
+        Combinations {
+            columns,
+            indexes: (0..size).collect(),
+            done: size > columns.len(),
+        }
+    }
+}
+
+impl Iterator for Combinations<'_> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let combo = self.indexes.iter().map(|&i| self.columns[i]).collect();
+        // move the last index that has room, and line up those after it
+        let (n, k) = (self.columns.len(), self.indexes.len());
+        match (0..k).rev().find(|&i| self.indexes[i] < n - k + i) {
+            Some(i) => {
+                self.indexes[i] += 1;
+                for j in i + 1..k {
+                    self.indexes[j] = self.indexes[j - 1] + 1;
+                }
+            }
+            None => self.done = true,
+        }
+        Some(combo)
+    }
+}
+
+/// Find the smallest column combinations whose values are unique
+pub fn find_keys(
+    csvfile: &PathBuf,
+    field_sep: Option<u8>,
+    row_sep: Option<u8>,
+    sqltypes: &[SQLType],
+    max_columns: usize,
+    memory_cap: usize,
+) -> Result<KeyCandidates> {
+    let mut found = KeyCandidates::default();
+    let singles: Vec<Vec<usize>> = (0..sqltypes.len())
+        .filter(|&i| eligible(&sqltypes[i]))
+        .map(|i| vec![i])
+        .collect();
+    if max_columns == 0 || singles.is_empty() {
+        return Ok(found);
+    }
+    let (candidates, rows) = check(csvfile, field_sep, row_sep, singles, memory_cap, &mut found)?;
+    if rows == 0 {
+        return Ok(found);
+    }
+    let mut distinct = vec![usize::MAX; sqltypes.len()];
+    let mut present = Vec::new();
+    for candidate in &candidates {
+        let column = candidate.columns[0];
+        if candidate.unique {
+            found.unique.push(candidate.columns.clone());
+        } else if !candidate.blank {
+            present.push(column);
+            if let Some(seen) = &candidate.seen {
+                distinct[column] = seen.len();
+            }
+        }
+    }
+    for size in 2..=max_columns {
+        if !found.unique.is_empty() {
+            break;
+        }
+        let mut viable = Combinations::new(&present, size).filter(|combo| {
+            combo
+                .iter()
+                .map(|&i| distinct[i])
+                .fold(1usize, |product, count| product.saturating_mul(count))
+                >= rows
+        });
+        let combos: Vec<Vec<usize>> = viable.by_ref().take(MAX_COMBINATIONS).collect();
+        let skipped = viable.take(MAX_COMBINATIONS + 1).count();
+        found.unchecked += skipped.min(MAX_COMBINATIONS);
+        found.unchecked_more |= skipped > MAX_COMBINATIONS;
+        if combos.is_empty() {
+            continue;
+        }
+        let (candidates, _) = check(csvfile, field_sep, row_sep, combos, memory_cap, &mut found)?;
+        found.unique.extend(
+            candidates
+                .into_iter()
+                .filter(|c| c.unique)
+                .map(|c| c.columns),
+        );
+    }
+    Ok(found)
+}
@@ -5,8 +5,9 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use csv::{Reader, ReaderBuilder, Terminator};
+use serde::{Deserialize, Serialize};
 use simdutf8::basic::from_utf8;
 use std::ffi::OsString;
 use std::fs;
@@ -15,14 +16,89 @@ use std::io::{BufWriter, Write};
 use std::iter::zip;
 use std::path::{Path, PathBuf};
 
+pub mod bcp;
+pub mod categories;
+pub mod create;
+pub mod descriptions;
+pub mod dialect;
+pub mod diff;
 pub mod infer;
+pub mod keys;
 mod keywords;
+pub mod mysql;
+pub mod oracle;
+pub mod overrides;
+pub mod relations;
+pub mod sqlite;
 pub mod view;
+pub mod zeros;
 //pub mod reader;
 
-type HeaderGen = fn(&mut BufWriter<File>, &str, &[String]) -> Result<()>;
+type HeaderGen = fn(&mut BufWriter<File>, &str, &[String], &[infer::SQLType]) -> Result<()>;
 type FooterGen = fn(&mut BufWriter<File>, &str, &[String], &[infer::SQLType]) -> Result<()>;
 type FieldProcessor = fn(&mut BufWriter<File>, &str, &[u8]) -> Result<()>;
+type BinaryEncoder = fn(&[u8], &infer::SQLType) -> Option<String>;
+type LoadGen = fn(&Path, &str, &[String], &[infer::SQLType], &[u8], &[u8]) -> String;
+type ControlGen = fn(&[PathBuf], &str, &[String], &[infer::SQLType], &[u8], &[u8]) -> String;
+type FormatGen = fn(&Path, &[String], &[usize], &[u8], &[u8]) -> Result<()>;
+
+/// How the files `csv_into` writes are loaded into the database
+enum Loader {
+    /// a statement for each file, printed after the schema
+    Statement(LoadGen),
+    /// one control file for all of them, written beside the first
+    ControlFile(ControlGen),
+    /// format files written beside each of them
+    FormatFiles(FormatGen),
+}
+
+/// Opt-in behavior for inference and the schema built from it
+#[derive(Debug, Default)]
+pub struct SchemaOptions {
+    /// detect integer columns holding Unix epoch timestamps
+    pub epoch: bool,
+    /// keep the original integer of an epoch column as `<column>_raw`
+    pub keep_epoch: bool,
+    /// column types pinned by the user instead of inferred
+    pub overrides: overrides::Overrides,
+    /// precede the schema with comments showing which values set each type
+    pub explain: bool,
+    /// add PRIMARY KEY and UNIQUE constraints for columns unique in the data
+    pub keys: bool,
+    /// restrict columns with few distinct values to the values seen
+    pub categories: Option<categories::Categories>,
+    /// how to type numbers too large for BIGINT or NUMERIC
+    pub out_of_range: infer::limits::RangePolicy,
+    /// how to read numbers with leading zeros, unless overridden per column
+    pub leading_zeros: zeros::ZeroPolicy,
+    /// the type checks values are inferred with, built-in unless extended
+    pub checks: infer::Registry,
+    /// add a GEOGRAPHY point computed from each latitude and longitude pair
+    pub points: bool,
+    /// how to store columns of durations
+    pub durations: infer::duration::DurationPolicy,
+    /// the database the schema and output are written for
+    pub dialect: dialect::Dialect,
+    /// how columns named with reserved words are written
+    pub identifiers: dialect::IdentifierPolicy,
+    /// schema of a table named without one
+    pub schema: Option<String>,
+    /// how the table is made ready for the rows
+    pub create: create::CreateStrategy,
+    /// describe columns by their raw headers, or as the descriptions say
+    pub descriptions: Option<descriptions::Descriptions>,
+}
+
+impl SchemaOptions {
+    /// The table a name refers to
+    pub fn table(&self, tablename: &str) -> Result<dialect::TableName> {
+        let table = tablename
+            .parse::<dialect::TableName>()?
+            .or_schema(self.schema.as_deref());
+        table.validate(self.dialect)?;
+        Ok(table)
+    }
+}
 
 struct OutputConfig {
     row_sep: Vec<u8>,
@@ -30,6 +106,8 @@ struct OutputConfig {
     field_processor: FieldProcessor,
     page_header: Option<HeaderGen>,
     page_footer: Option<FooterGen>,
+    binary_encoder: BinaryEncoder,
+    loader: Option<Loader>,
 }
 
 /*
@@ -92,6 +170,8 @@ pub fn csv_columns(
     raw: bool,
     field_sep: Option<u8>,
     row_sep: Option<u8>,
+    dialect: dialect::Dialect,
+    identifiers: dialect::IdentifierPolicy,
 ) -> Result<Vec<String>> {
     let mut rdr = csv_reader(csvfile, field_sep, row_sep)?;
     let headers = rdr.headers()?;
@@ -105,20 +185,28 @@ pub fn csv_columns(
                     .chars()
                     .map(|c| if c.is_alphanumeric() { c } else { '_' })
                     .collect();
+                let clean_chars = dialect.truncate(&clean_chars, 0).to_string();
 
-                if keywords::KEYWORDS.contains(&&clean_chars.to_ascii_lowercase()[..]) {
-                    let prefix: char;
+                if dialect.is_reserved(&clean_chars)
+                    && identifiers != dialect::IdentifierPolicy::Quote
+                {
+                    let letter: char;
                     if let Some(tablename) = &tablename {
-                        prefix = tablename.chars().next().unwrap_or('x');
+                        letter = tablename.chars().next().unwrap_or('x');
                     } else {
-                        prefix = csvfile
+                        letter = csvfile
                             .to_str()
                             .unwrap_or("x")
                             .chars()
                             .next()
                             .unwrap_or('x');
                     }
-                    format!("{}_{}", prefix, clean_chars)
+                    let name = dialect.truncate(&clean_chars, letter.len_utf8() + 1);
+                    if identifiers == dialect::IdentifierPolicy::Suffix {
+                        format!("{}_{}", name, letter)
+                    } else {
+                        format!("{}_{}", letter, name)
+                    }
                 } else {
                     clean_chars
                 }
@@ -135,7 +223,12 @@ pub fn csv_columns(
         let previous = indexes[i - 1];
         if new_headers[current] == reference[previous] {
             while counter < u16::MAX {
-                let new_name = format!("{}_{}", new_headers[current], &counter);
+                let suffix = format!("_{}", &counter);
+                let new_name = format!(
+                    "{}{}",
+                    dialect.truncate(&new_headers[current], suffix.len()),
+                    suffix
+                );
                 if !new_headers.contains(&new_name) {
                     new_headers[current] = new_name;
                     break;
@@ -149,7 +242,7 @@ pub fn csv_columns(
     Ok(new_headers)
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 pub struct CsvStats {
     column_count: usize,
     row_count: usize,
@@ -158,6 +251,96 @@ pub struct CsvStats {
     column_char_lengths: Vec<usize>,
     column_byte_lengths: Vec<usize>,
     column_types: Option<Vec<infer::SQLType>>,
+    column_distinct: Vec<categories::Distinct>,
+}
+
+/// Larger of each pair of lengths, where both sides measured them
+fn merge_lengths(lengths: &mut Vec<usize>, others: &[usize]) {
+    if lengths.is_empty() || others.is_empty() {
+        lengths.clear();
+    } else {
+        for (length, other) in zip(lengths, others) {
+            *length = (*other).max(*length);
+        }
+    }
+}
+
+impl CsvStats {
+    /// Read stats saved by [`CsvStats::save`]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read inference state from {:?}", path))?;
+        let mut stats: CsvStats = serde_json::from_str(&text)
+            .with_context(|| format!("Failed to parse inference state in {:?}", path))?;
+        for sqltype in stats.column_types.iter_mut().flatten() {
+            sqltype.reindex();
+        }
+        Ok(stats)
+    }
+
+    /// Write the stats as JSON, to be merged with others later
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let text = serde_json::to_string_pretty(self)
+            .with_context(|| format!("Failed to save inference state to {:?}", path))?;
+        fs::write(path, text)
+            .with_context(|| format!("Failed to write inference state to {:?}", path))
+    }
+
+    /// Fold in the stats of another file of the same table
+    pub fn merge(&mut self, other: &CsvStats) -> Result<()> {
+        if self.columns != other.columns {
+            bail!(
+                "Cannot merge stats with different columns: {} and {}",
+                self.columns.join(", "),
+                other.columns.join(", ")
+            );
+        }
+        self.row_count += other.row_count;
+        merge_lengths(&mut self.column_char_lengths, &other.column_char_lengths);
+        merge_lengths(&mut self.column_byte_lengths, &other.column_byte_lengths);
+        self.column_types = match (self.column_types.take(), &other.column_types) {
+            (Some(mut sqltypes), Some(others)) => {
+                for (sqltype, other) in zip(&mut sqltypes, others) {
+                    sqltype.merge(other);
+                }
+                Some(sqltypes)
+            }
+            _ => None,
+        };
+        if self.column_distinct.is_empty() || other.column_distinct.is_empty() {
+            self.column_distinct.clear();
+        } else {
+            for (distinct, other) in zip(&mut self.column_distinct, &other.column_distinct) {
+                distinct.merge(other);
+            }
+        }
+        Ok(())
+    }
+
+    /// The `CREATE TABLE` statement for the inferred column types
+    pub fn schema(&self, tablename: &str, options: &SchemaOptions) -> Result<String> {
+        let Some(sqltypes) = &self.column_types else {
+            bail!("No column types were inferred for {}", tablename);
+        };
+        let dialect = options.dialect;
+        let table = options.table(tablename)?;
+        let target = options.create.target(&table, dialect);
+        let schema = schema_string(&self.columns, sqltypes, dialect);
+        let (clear, create) = options.create.statements(&target, &schema, dialect);
+        let Some(descriptions) = &options.descriptions else {
+            return Ok(format!("{}{}", clear, create));
+        };
+        let described = descriptions::statements(
+            &target,
+            &self.columns,
+            sqltypes,
+            &descriptions.resolve(&self.raw_columns, &self.columns)?,
+            dialect,
+        )?;
+        Ok(format!("{}{}\n{}", clear, create, described.trim_end()))
+    }
 }
 
 pub fn csv_survey(
@@ -167,26 +350,49 @@ pub fn csv_survey(
     tablename: Option<&str>,
     field_sep: Option<u8>,
     row_sep: Option<u8>,
+    options: &SchemaOptions,
 ) -> Result<CsvStats> {
     let mut stats = CsvStats {
         ..Default::default()
     };
-    stats.columns = csv_columns(csvfile, tablename, false, field_sep, row_sep)?;
-    stats.raw_columns = csv_columns(csvfile, tablename, true, field_sep, row_sep)?;
+    stats.columns = csv_columns(
+        csvfile,
+        tablename,
+        false,
+        field_sep,
+        row_sep,
+        options.dialect,
+        options.identifiers,
+    )?;
+    stats.raw_columns = csv_columns(
+        csvfile,
+        tablename,
+        true,
+        field_sep,
+        row_sep,
+        options.dialect,
+        options.identifiers,
+    )?;
     stats.column_count = stats.columns.len();
     if utf8 {
         stats.column_char_lengths = stats.columns.iter().map(|x| x.chars().count()).collect();
     }
     stats.column_byte_lengths = stats.columns.iter().map(|x| x.len()).collect();
+    if let Some(categories) = options.categories {
+        stats.column_distinct =
+            vec![categories::Distinct::new(categories.limit); stats.column_count];
+    }
 
     let mut rdr = csv_reader(csvfile, field_sep, row_sep)?;
     if infer {
-        stats.column_types = Some(vec![
-            infer::SQLType {
-                ..Default::default()
-            };
-            stats.column_count
-        ]);
+        stats.column_types = Some(csv_infer(
+            csvfile,
+            &stats.columns,
+            field_sep,
+            row_sep,
+            options,
+            None,
+        )?);
     }
 
     for result in rdr.byte_records() {
@@ -198,49 +404,547 @@ pub fn csv_survey(
                     stats.column_char_lengths[i].max(from_utf8(value)?.chars().count());
             }
             stats.column_byte_lengths[i] = stats.column_byte_lengths[i].max(value.len());
-            if infer {
-                let Some(ref mut sqltypes) = stats.column_types else { todo!() };
-                if let Some(sqltype) = infer::infer(value, sqltypes[i].index, sqltypes[i].subindex)
-                {
-                    sqltypes[i].merge(&sqltype);
-                }
+            if let Some(distinct) = stats.column_distinct.get_mut(i) {
+                distinct.update(value);
             }
         }
     }
     Ok(stats)
 }
 
-pub fn csv_schema(csvfile: &PathBuf, tablename: &str, ascii_delimited: bool) -> Result<String> {
-    let field_sep: Option<u8>;
-    let row_sep: Option<u8>;
-    if ascii_delimited {
-        field_sep = Some(b'\x1F');
-        row_sep = Some(b'\x1E');
-    } else {
-        field_sep = None;
-        row_sep = None;
+/// Report the column combinations that could serve as keys, as constraints
+pub fn csv_keys(csvfile: &PathBuf, max_columns: usize, memory_cap: usize) -> Result<String> {
+    let headers = csv_columns(
+        csvfile,
+        None,
+        false,
+        None,
+        None,
+        Default::default(),
+        Default::default(),
+    )?;
+    let mut sqltypes = csv_infer(csvfile, &headers, None, None, &Default::default(), None)?;
+    let keys = keys::find_keys(csvfile, None, None, &sqltypes, max_columns, memory_cap)?;
+    let mut report = String::new();
+    if keys.approximate {
+        report.push_str("-- some keys were confirmed by hash, not exact value\n");
+    }
+    if keys.unchecked > 0 {
+        report.push_str(&format!(
+            "-- {}{} combinations were not checked\n",
+            if keys.unchecked_more { "at least " } else { "" },
+            keys.unchecked
+        ));
+    }
+    for constraint in key_constraints(&headers, &mut sqltypes, &keys) {
+        report.push_str(&constraint);
+        report.push('\n');
+    }
+    Ok(report)
+}
+
+/// Note the columns whose values are zero-padded digits of one width
+pub fn csv_padding(csvfile: &PathBuf) -> Result<String> {
+    let headers = csv_columns(
+        csvfile,
+        None,
+        false,
+        None,
+        None,
+        Default::default(),
+        Default::default(),
+    )?;
+    let mut padding = vec![zeros::Padding::default(); headers.len()];
+    let mut rdr = csv_reader(csvfile, None, None)?;
+    let mut row = csv::ByteRecord::new();
+    while rdr.read_byte_record(&mut row)? {
+        for (column, value) in zip(&mut padding, row.iter()) {
+            column.update(value);
+        }
+    }
+    let mut report = String::new();
+    for (column, padding) in zip(&headers, &padding) {
+        if let Some(note) = padding.report(column) {
+            report.push_str(&note);
+            report.push('\n');
+        }
+    }
+    Ok(report)
+}
+
+/// CSV files named directly, plus those found in any directories given
+fn csv_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let mut found: Vec<PathBuf> = fs::read_dir(path)
+                .with_context(|| format!("Failed to read directory {:?}", path))?
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|file| {
+                    file.is_file()
+                        && file
+                            .extension()
+                            .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"))
+                })
+                .collect();
+            found.sort();
+            files.append(&mut found);
+        } else {
+            files.push(path.clone());
+        }
+    }
+    Ok(files)
+}
+
+/// Table name for a CSV file, from its file name
+fn table_name(csvfile: &Path) -> String {
+    csvfile
+        .file_stem()
+        .map(|stem| stem.to_string_lossy())
+        .unwrap_or_default()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Suggest FOREIGN KEY constraints between a set of CSV files
+pub fn csv_relations(paths: &[PathBuf], memory_cap: usize) -> Result<String> {
+    let mut tables = Vec::new();
+    for path in csv_files(paths)? {
+        let name = table_name(&path);
+        let columns = csv_columns(
+            &path,
+            Some(&name),
+            false,
+            None,
+            None,
+            Default::default(),
+            Default::default(),
+        )?;
+        let sqltypes = csv_infer(&path, &columns, None, None, &Default::default(), None)?;
+        let keys = keys::find_keys(&path, None, None, &sqltypes, 1, memory_cap)?;
+        tables.push(relations::Table {
+            name,
+            path,
+            columns,
+            sqltypes,
+            keys: keys.unique.into_iter().map(|key| key[0]).collect(),
+        });
+    }
+    let found = relations::find_foreign_keys(&tables, None, None, memory_cap)?;
+    let names = |indexes: &[usize]| {
+        indexes
+            .iter()
+            .map(|&t| tables[t].name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut report = String::new();
+    if found.unchecked > 0 {
+        report.push_str(&format!(
+            "-- {} key columns were too large to check\n",
+            found.unchecked
+        ));
+    }
+    report.push_str(&format!("-- load order: {}\n", names(&found.load_order)));
+    if !found.cyclic.is_empty() {
+        report.push_str(&format!(
+            "-- reference cycle, load without constraints: {}\n",
+            names(&found.cyclic)
+        ));
+    }
+    let dialect = dialect::Dialect::SqlServer;
+    for &t in found.load_order.iter().chain(&found.cyclic) {
+        for fk in found.foreign_keys.iter().filter(|fk| fk.table == t) {
+            let parent = &tables[fk.parent];
+            report.push_str(&format!(
+                "ALTER TABLE {} ADD FOREIGN KEY ({}) REFERENCES {} ({});\n",
+                dialect.identifier(&tables[t].name),
+                dialect.identifier(&tables[t].columns[fk.column]),
+                dialect.identifier(&parent.name),
+                dialect.identifier(&parent.columns[fk.parent_column])
+            ));
+        }
+    }
+    Ok(report)
+}
+
+/// PRIMARY KEY and UNIQUE constraints for the keys found
+fn key_constraints(
+    columns: &[String],
+    sqltypes: &mut [infer::SQLType],
+    keys: &keys::KeyCandidates,
+) -> Vec<String> {
+    let names = |key: &[usize]| {
+        key.iter()
+            .map(|&i| columns[i].as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut constraints = Vec::new();
+    let primary = keys.primary(sqltypes).map(<[usize]>::to_vec);
+    if let Some(primary) = &primary {
+        constraints.push(format!("PRIMARY KEY ({})", names(primary)));
+        for &i in primary {
+            sqltypes[i].not_null = true;
+        }
     }
-    let headers = csv_columns(csvfile, Some(tablename), false, field_sep, row_sep)?;
+    for key in &keys.unique {
+        if Some(key) != primary.as_ref() {
+            constraints.push(format!("UNIQUE ({})", names(key)));
+        }
+    }
+    constraints
+}
+
+fn csv_infer(
+    csvfile: &PathBuf,
+    columns: &[String],
+    field_sep: Option<u8>,
+    row_sep: Option<u8>,
+    options: &SchemaOptions,
+    mut explanations: Option<&mut [infer::Explanation]>,
+) -> Result<Vec<infer::SQLType>> {
     let mut rdr = csv_reader(csvfile, field_sep, row_sep)?;
-    let row_length: usize = headers.len();
+    let raw_columns: Vec<String> = rdr.headers()?.iter().map(str::to_string).collect();
+    let overrides = options.overrides.resolve(&raw_columns, columns)?;
+    let policies: Vec<zeros::ZeroPolicy> = overrides
+        .iter()
+        .map(|o| {
+            o.as_ref()
+                .and_then(|o| o.leading_zeros)
+                .unwrap_or(options.leading_zeros)
+        })
+        .collect();
     let mut sqltypes: Vec<infer::SQLType> = vec![
         infer::SQLType {
             ..Default::default()
         };
-        row_length
+        columns.len()
     ];
+    let mut ranges = vec![infer::epoch::IntegerRange::default(); columns.len()];
     let mut row = csv::ByteRecord::new();
     while rdr.read_byte_record(&mut row)? {
+        let line = row.position().map_or(0, |p| p.line());
         for (i, value) in row.iter().enumerate() {
-            if let Some(sqltype) = infer::infer(value, sqltypes[i].index, sqltypes[i].subindex) {
-                sqltypes[i].merge(&sqltype);
+            let (index, subindex) = (sqltypes[i].index, sqltypes[i].subindex);
+            if let Some(sqltype) = policies[i].infer(&options.checks, value, index, subindex) {
+                let before = explanations.is_some().then(|| sqltypes[i].clone());
+                options.checks.merge(&mut sqltypes[i], &sqltype);
+                if let (Some(explanations), Some(before)) = (explanations.as_deref_mut(), before) {
+                    explanations[i].record(line, value, &before, &sqltypes[i]);
+                }
+            }
+            if options.epoch {
+                ranges[i].update(value);
+            }
+            if let Some(column_override) = &overrides[i] {
+                if let Err(err) = column_override.check(value) {
+                    anyhow::bail!(
+                        "Column {} on line {} contradicts its override: {}",
+                        columns[i],
+                        line,
+                        err
+                    );
+                }
+            }
+        }
+    }
+    if options.epoch {
+        for (column, (sqltype, range)) in zip(columns, zip(&mut sqltypes, &ranges)) {
+            if let Some(unit) = infer::epoch::detect(column, sqltype, range) {
+                sqltype.set_epoch(unit);
             }
         }
     }
-    let schema = schema_string(&headers, &sqltypes);
+    for (sqltype, column_override) in zip(&mut sqltypes, &overrides) {
+        options.out_of_range.apply(sqltype);
+        options.durations.apply(sqltype);
+        if let Some(column_override) = column_override {
+            column_override.apply(sqltype);
+        }
+    }
+    Ok(sqltypes)
+}
+
+/// Latitude and longitude columns whose values are all in range
+fn coordinate_pairs(
+    csvfile: &PathBuf,
+    columns: &[String],
+    sqltypes: &[infer::SQLType],
+    field_sep: Option<u8>,
+    row_sep: Option<u8>,
+) -> Result<Vec<infer::geo::CoordinatePair>> {
+    let mut pairs = infer::geo::coordinate_pairs(columns, sqltypes);
+    pairs.retain(|pair| !columns.contains(&pair.name()));
+    if pairs.is_empty() {
+        return Ok(pairs);
+    }
+    let mut bounds = vec![infer::geo::Bounds::default(); pairs.len()];
+    let mut rdr = csv_reader(csvfile, field_sep, row_sep)?;
+    let mut row = csv::ByteRecord::new();
+    while rdr.read_byte_record(&mut row)? {
+        for (pair, bounds) in zip(&pairs, &mut bounds) {
+            bounds.update(
+                row.get(pair.latitude).unwrap_or_default(),
+                row.get(pair.longitude).unwrap_or_default(),
+            );
+        }
+    }
+    Ok(zip(pairs, bounds)
+        .filter(|(_, bounds)| bounds.fits)
+        .map(|(pair, _)| pair)
+        .collect())
+}
+
+/// Columns as written to the table, with the CSV field each is drawn from
+fn table_columns(
+    columns: &[String],
+    sqltypes: &[infer::SQLType],
+    options: &SchemaOptions,
+) -> (Vec<String>, Vec<infer::SQLType>, Vec<usize>) {
+    let mut names = Vec::with_capacity(columns.len());
+    let mut types = Vec::with_capacity(columns.len());
+    let mut sources = Vec::with_capacity(columns.len());
+    for (i, (column, sqltype)) in zip(columns, sqltypes).enumerate() {
+        names.push(column.clone());
+        types.push(sqltype.clone());
+        sources.push(i);
+        if options.keep_epoch && sqltype.epoch.is_some() {
+            names.push(format!("{}_raw", column));
+            types.push(infer::SQLType {
+                name: infer::SQLTypeName::Bigint,
+                byte_length: sqltype.byte_length,
+                ..Default::default()
+            });
+            sources.push(i);
+        }
+    }
+    (names, types, sources)
+}
+
+/// The statements describing each column of the table
+fn column_descriptions(
+    csvfile: &PathBuf,
+    table: &dialect::TableName,
+    headers: &[String],
+    (columns, sqltypes, sources): (&[String], &[infer::SQLType], &[usize]),
+    field_sep: Option<u8>,
+    row_sep: Option<u8>,
+    options: &SchemaOptions,
+) -> Result<String> {
+    let Some(descriptions) = &options.descriptions else {
+        return Ok(String::new());
+    };
+    let raw_columns = csv_columns(
+        csvfile,
+        None,
+        true,
+        field_sep,
+        row_sep,
+        options.dialect,
+        options.identifiers,
+    )?;
+    let resolved = descriptions.resolve(&raw_columns, headers)?;
+    let described: Vec<Option<String>> = sources
+        .iter()
+        .enumerate()
+        .map(|(i, &source)| {
+            let raw = i > 0 && sources[i - 1] == source;
+            resolved[source].clone().filter(|_| !raw)
+        })
+        .collect();
+    descriptions::statements(table, columns, sqltypes, &described, options.dialect)
+}
+
+pub fn csv_schema(
+    csvfile: &PathBuf,
+    tablename: &str,
+    ascii_delimited: bool,
+    options: &SchemaOptions,
+) -> Result<String> {
+    let field_sep: Option<u8>;
+    let row_sep: Option<u8>;
+    if ascii_delimited {
+        field_sep = Some(b'\x1F');
+        row_sep = Some(b'\x1E');
+    } else {
+        field_sep = None;
+        row_sep = None;
+    }
+    let table = options.table(tablename)?;
+    let headers = csv_columns(
+        csvfile,
+        Some(&table.table),
+        false,
+        field_sep,
+        row_sep,
+        options.dialect,
+        options.identifiers,
+    )?;
+    let mut explanations = vec![infer::Explanation::default(); headers.len()];
+    let mut sqltypes = csv_infer(
+        csvfile,
+        &headers,
+        field_sep,
+        row_sep,
+        options,
+        options.explain.then_some(&mut explanations[..]),
+    )?;
+    let dialect = options.dialect;
+    let names = dialect.identifiers(&headers);
+    let mut constraints = if options.keys {
+        let keys = keys::find_keys(
+            csvfile,
+            field_sep,
+            row_sep,
+            &sqltypes,
+            2,
+            keys::DEFAULT_MEMORY_CAP,
+        )?;
+        key_constraints(&names, &mut sqltypes, &keys)
+    } else {
+        Vec::new()
+    };
+    let points = if options.points {
+        coordinate_pairs(csvfile, &headers, &sqltypes, field_sep, row_sep)?
+    } else {
+        Vec::new()
+    };
+    for (column, sqltype) in zip(&names, &sqltypes) {
+        if sqltype.name == infer::SQLTypeName::Json {
+            constraints.extend(dialect.json_check(column));
+        }
+    }
+    let mut lookups = String::new();
+    if let Some(categories) = options.categories {
+        let stats = csv_survey(
+            csvfile,
+            false,
+            false,
+            Some(&table.table),
+            field_sep,
+            row_sep,
+            options,
+        )?;
+        for (i, (sqltype, distinct)) in zip(&sqltypes, &stats.column_distinct).enumerate() {
+            let Some(values) = distinct.categories(sqltype) else {
+                continue;
+            };
+            let column = &names[i];
+            match categories.style {
+                categories::CategoryStyle::Check => {
+                    constraints.push(categories::check_constraint(column, &values, sqltype));
+                }
+                categories::CategoryStyle::Lookup => {
+                    let strategy = options.create.lookup();
+                    let lookup = strategy.target(
+                        &table.sibling(&categories::lookup_name(&table.table, &headers[i])),
+                        dialect,
+                    );
+                    let (clear, create) = strategy.statements(
+                        &lookup,
+                        &categories::lookup_definition(column, &dialect.type_name(sqltype)),
+                        dialect,
+                    );
+                    let lookup = lookup.qualified(dialect);
+                    lookups.push_str(&clear);
+                    lookups.push_str(&create);
+                    lookups.push('\n');
+                    lookups.push_str(&categories::lookup_rows(
+                        &lookup,
+                        column,
+                        &values,
+                        sqltype,
+                        dialect,
+                        strategy == create::CreateStrategy::IfNotExists,
+                    ));
+                    constraints.push(categories::foreign_key(&lookup, column));
+                }
+            }
+        }
+    }
+    let explained = if options.explain {
+        explain_string(&headers, &sqltypes, &explanations, dialect)
+    } else {
+        String::new()
+    };
+    let (columns, sqltypes, sources) = table_columns(&headers, &sqltypes, options);
+    let mut schema = schema_string(&columns, &sqltypes, dialect);
+    for point in &points {
+        schema.push_str(", ");
+        schema.push_str(&point.computed_column(&names, dialect));
+    }
+    for constraint in constraints {
+        schema.push_str(", ");
+        schema.push_str(&constraint);
+    }
+    let target = options.create.target(&table, dialect);
+    let (clear, create) = options.create.statements(&target, &schema, dialect);
+    let described = column_descriptions(
+        csvfile,
+        &target,
+        &headers,
+        (&columns, &sqltypes, &sources),
+        field_sep,
+        row_sep,
+        options,
+    )?;
     Ok(format!(
-        "DROP TABLE IF EXISTS {0};\nCREATE TABLE {0} ({1});",
-        tablename, schema
+        "{}{}{}{}{}",
+        explained,
+        clear,
+        lookups,
+        create,
+        if described.is_empty() {
+            described
+        } else {
+            format!("\n{}", described.trim_end())
+        }
+    ))
+}
+
+/// The `ALTER TABLE` statements bringing an existing table up to the CSV file
+pub fn csv_diff(
+    csvfile: &PathBuf,
+    definition: &Path,
+    tablename: Option<&str>,
+    options: &SchemaOptions,
+) -> Result<String> {
+    if options.dialect != dialect::Dialect::SqlServer {
+        bail!("Tables can only be compared with SQL Server definitions for now");
+    }
+    let (table, existing) = if definition
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+    {
+        let Some(tablename) = tablename else {
+            bail!("A table name is needed to compare with a state file");
+        };
+        let stats = CsvStats::load(definition)?;
+        (options.table(tablename)?, diff::state_columns(&stats)?)
+    } else {
+        let text = fs::read_to_string(definition)
+            .with_context(|| format!("Failed to read table definition from {:?}", definition))?;
+        let (table, columns) = diff::parse_create_table(&text)
+            .with_context(|| format!("Failed to parse table definition in {:?}", definition))?;
+        match tablename {
+            Some(tablename) => (options.table(tablename)?, columns),
+            None => (table, columns),
+        }
+    };
+    let headers = csv_columns(
+        csvfile,
+        Some(&table.table),
+        false,
+        None,
+        None,
+        dialect::Dialect::SqlServer,
+        options.identifiers,
+    )?;
+    let sqltypes = csv_infer(csvfile, &headers, None, None, options, None)?;
+    Ok(diff::alter_statements(
+        &table, &existing, &headers, &sqltypes,
     ))
 }
 
@@ -265,6 +969,7 @@ fn field_processor_json(stream: &mut BufWriter<File>, column: &str, value: &[u8]
                 b'\'' => stream.write_all(b"''")?,
                 b'\r' => stream.write_all(b"")?,
                 b'\n' => stream.write_all(b"\\n")?,
+                b'\t' => stream.write_all(b"\\t")?,
                 _ => stream.write_all(&[*char])?,
             }
         }
@@ -273,10 +978,29 @@ fn field_processor_json(stream: &mut BufWriter<File>, column: &str, value: &[u8]
     Ok(())
 }
 
+/// A field in PostgreSQL's COPY text format, where a blank is NULL
+fn field_processor_copy(stream: &mut BufWriter<File>, _column: &str, value: &[u8]) -> Result<()> {
+    if value.is_empty() {
+        stream.write_all(b"\\N")?;
+        return Ok(());
+    }
+    for char in value {
+        match char {
+            b'\\' => stream.write_all(b"\\\\")?,
+            b'\t' => stream.write_all(b"\\t")?,
+            b'\r' => stream.write_all(b"\\r")?,
+            b'\n' => stream.write_all(b"\\n")?,
+            _ => stream.write_all(&[*char])?,
+        }
+    }
+    Ok(())
+}
+
 fn page_header_bcp(
     stream: &mut BufWriter<File>,
     _tablename: &str,
     columns: &[String],
+    _sqltypes: &[infer::SQLType],
 ) -> Result<()> {
     for (i, col) in columns.iter().enumerate() {
         if i > 0 {
@@ -292,18 +1016,69 @@ fn page_header_json(
     stream: &mut BufWriter<File>,
     tablename: &str,
     columns: &[String],
+    sqltypes: &[infer::SQLType],
 ) -> Result<()> {
+    let dialect = dialect::Dialect::SqlServer;
     write!(stream, "INSERT INTO {}\nSELECT\n", tablename)?;
-    for (i, col) in columns.iter().enumerate() {
+    for (i, (col, sqltype)) in zip(columns, sqltypes).enumerate() {
         if i > 0 {
             writeln!(stream, ",")?;
         }
-        write!(stream, "    {}", col)?;
+        let col = dialect.identifier(col);
+        // spatial columns arrive as WKT text
+        match infer::geo::from_text(&col, sqltype) {
+            Some(converted) => write!(stream, "    {}", converted)?,
+            None => write!(stream, "    {}", col)?,
+        }
     }
     write!(stream, "\nFROM OPENJSON('[ \\\n    {{")?;
     Ok(())
 }
 
+fn page_header_json_postgres(
+    stream: &mut BufWriter<File>,
+    tablename: &str,
+    columns: &[String],
+    sqltypes: &[infer::SQLType],
+) -> Result<()> {
+    let dialect = dialect::Dialect::Postgres;
+    write!(stream, "INSERT INTO {}\nSELECT\n", tablename)?;
+    for (i, (col, sqltype)) in zip(columns, sqltypes).enumerate() {
+        if i > 0 {
+            writeln!(stream, ",")?;
+        }
+        write!(stream, "    {}", dialect.identifier(col))?;
+        // a jsonb field would take the JSON string itself, not what it holds
+        if sqltype.name == infer::SQLTypeName::Json {
+            write!(stream, "::jsonb")?;
+        }
+    }
+    write!(stream, "\nFROM json_to_recordset('[\n    {{")?;
+    Ok(())
+}
+
+fn page_footer_json_postgres(
+    stream: &mut BufWriter<File>,
+    _tablename: &str,
+    columns: &[String],
+    sqltypes: &[infer::SQLType],
+) -> Result<()> {
+    let dialect = dialect::Dialect::Postgres;
+    let record = zip(columns, sqltypes)
+        .map(|(col, sqltype)| {
+            let type_name = if sqltype.name == infer::SQLTypeName::Json {
+                "text".to_string()
+            } else {
+                dialect.type_name(sqltype)
+            };
+            format!("{} {}", dialect.identifier(col), type_name)
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    writeln!(stream, "}}\n]') AS x({});", record)?;
+    Ok(())
+}
+
 fn page_footer_json(
     stream: &mut BufWriter<File>,
     _tablename: &str,
@@ -321,33 +1096,94 @@ pub fn csv_into_bcp(
     tablename: &str,
     infer: bool,
     page_size: usize,
+    options: &SchemaOptions,
 ) -> Result<()> {
-    let conf = OutputConfig {
-        row_sep: b"\x1E".to_vec(),
-        field_sep: b"\x1F".to_vec(),
-        field_processor: field_processor_bcp,
-        page_header: Some(page_header_bcp),
-        page_footer: None,
+    // bcp and SQL*Loader run in sessions of their own, which cannot see a
+    // temporary table made in the session running the script
+    let temp = options.create == create::CreateStrategy::Temp;
+    let conf = match options.dialect {
+        dialect::Dialect::SqlServer if temp => {
+            bail!("bcp cannot fill a temporary table, so use --json with --create temp")
+        }
+        dialect::Dialect::Oracle { .. } if temp => {
+            bail!("SQL*Loader cannot fill a temporary table, so choose another --create")
+        }
+        dialect::Dialect::SqlServer => OutputConfig {
+            row_sep: b"\x1E".to_vec(),
+            field_sep: b"\x1F".to_vec(),
+            field_processor: field_processor_bcp,
+            page_header: Some(page_header_bcp),
+            page_footer: None,
+            binary_encoder: infer::binary::to_hex,
+            loader: Some(Loader::FormatFiles(bcp::format_files)),
+        },
+        // COPY reads tab-separated lines without a header
+        dialect::Dialect::Postgres => OutputConfig {
+            row_sep: b"\n".to_vec(),
+            field_sep: b"\t".to_vec(),
+            field_processor: field_processor_copy,
+            page_header: None,
+            page_footer: None,
+            binary_encoder: infer::binary::to_bytea,
+            loader: None,
+        },
+        // LOAD DATA skips the header and takes the same separators as bcp
+        dialect::Dialect::Mysql => OutputConfig {
+            row_sep: b"\x1E".to_vec(),
+            field_sep: b"\x1F".to_vec(),
+            field_processor: field_processor_bcp,
+            page_header: Some(page_header_bcp),
+            page_footer: None,
+            binary_encoder: infer::binary::to_hex,
+            loader: Some(Loader::Statement(mysql::load_data)),
+        },
+        // SQL*Loader reads files without a header
+        dialect::Dialect::Oracle { .. } => OutputConfig {
+            row_sep: b"\x1E".to_vec(),
+            field_sep: b"\x1F".to_vec(),
+            field_processor: field_processor_bcp,
+            page_header: None,
+            page_footer: None,
+            binary_encoder: infer::binary::to_hex,
+            loader: Some(Loader::ControlFile(oracle::control_file)),
+        },
+        dialect::Dialect::Sqlite => bail!("SQLite databases are filled with the load command"),
     };
-    csv_into(csvfile, filename, tablename, infer, page_size, conf)
+    csv_into(
+        csvfile, filename, tablename, infer, page_size, conf, options,
+    )
 }
 
 pub fn csv_into_bcp_fast(
     csvfile: &PathBuf,
     filename: &PathBuf,
-    _tablename: &str,
+    tablename: &str,
     _infer: bool,
     _page_size: usize,
+    options: &SchemaOptions,
 ) -> Result<()> {
+    if options.create != Default::default() {
+        bail!("The fast bcp output makes no table, so it cannot use a create strategy");
+    }
+    // the header carries the column names the table is created with
+    let table = options.table(tablename)?;
+    let headers = csv_columns(
+        csvfile,
+        Some(&table.table),
+        false,
+        None,
+        None,
+        dialect::Dialect::SqlServer,
+        options.identifiers,
+    )?;
     let mut rdr = ReaderBuilder::new()
-        .has_headers(false)
         .buffer_capacity(16384)
         .from_path(csvfile)
         .with_context(|| format!("Failed to read csv from {:?}", csvfile))?;
     let mut stream = new_file(filename, 0)?;
-    let mut new_page = true;
     let row_sep = b"\x1E".to_vec();
     let field_sep = b"\x1F".to_vec();
+    page_header_bcp(&mut stream, &table.table, &headers, &[])?;
 
     /*
     let mut wtr = WriterBuilder::new()
@@ -362,25 +1198,25 @@ pub fn csv_into_bcp_fast(
         wtr.write_byte_record(&r)?;
     }
     */
+    let mut lengths: Vec<usize> = headers.iter().map(String::len).collect();
     for result in rdr.byte_records() {
         //let row = result?;
-        if new_page {
-            new_page = false;
-        } else {
-            stream.write_all(&row_sep)?;
-        }
         let mut first = true;
-        for value in &result? {
+        for (i, value) in result?.iter().enumerate() {
             if first {
                 first = false;
             } else {
                 stream.write_all(&field_sep)?;
             }
+            if let Some(length) = lengths.get_mut(i) {
+                *length = value.len().max(*length);
+            }
             stream.write_all(value)?;
         }
+        stream.write_all(&row_sep)?;
     }
-    stream.write_all(&row_sep)?;
-    Ok(())
+    stream.flush()?;
+    bcp::format_files(filename, &headers, &lengths, &field_sep, &row_sep)
 }
 
 pub fn csv_into_json(
@@ -388,15 +1224,34 @@ pub fn csv_into_json(
     filename: &PathBuf,
     tablename: &str,
     page_size: usize,
+    options: &SchemaOptions,
 ) -> Result<()> {
-    let conf = OutputConfig {
-        row_sep: b"}, \\\n    {".to_vec(),
-        field_sep: b", ".to_vec(),
-        field_processor: field_processor_json,
-        page_header: Some(page_header_json),
-        page_footer: Some(page_footer_json),
+    let conf = match options.dialect {
+        dialect::Dialect::SqlServer => OutputConfig {
+            row_sep: b"}, \\\n    {".to_vec(),
+            field_sep: b", ".to_vec(),
+            field_processor: field_processor_json,
+            page_header: Some(page_header_json),
+            page_footer: Some(page_footer_json),
+            binary_encoder: infer::binary::to_base64,
+            loader: None,
+        },
+        dialect::Dialect::Postgres => OutputConfig {
+            row_sep: b"},\n    {".to_vec(),
+            field_sep: b", ".to_vec(),
+            field_processor: field_processor_json,
+            page_header: Some(page_header_json_postgres),
+            page_footer: Some(page_footer_json_postgres),
+            binary_encoder: infer::binary::to_bytea,
+            loader: None,
+        },
+        dialect::Dialect::Sqlite => bail!("SQLite databases are filled with the load command"),
+        dialect::Dialect::Mysql => bail!("MySQL tables are filled with LOAD DATA, without --json"),
+        dialect::Dialect::Oracle { .. } => {
+            bail!("Oracle tables are filled with SQL*Loader, without --json")
+        }
     };
-    csv_into(csvfile, filename, tablename, true, page_size, conf)
+    csv_into(csvfile, filename, tablename, true, page_size, conf, options)
 }
 
 fn indexed_file_path<T>(path: T, index: usize) -> PathBuf
@@ -417,11 +1272,14 @@ where
         stem.push("_");
         stem.push(index.to_string());
 
-        newpath.set_file_name(stem);
-
+        // the extension is added here, as setting it would replace any part
+        // of a stem like `sales.orders_2` after the dot
         if let Some(ext) = path.extension() {
-            newpath.set_extension(ext);
+            stem.push(".");
+            stem.push(ext);
         }
+
+        newpath.set_file_name(stem);
     }
     newpath
 }
@@ -447,8 +1305,8 @@ pub fn determine_output_path(
         fs::create_dir_all(outfile.clone())?;
     }
     if outfile.is_dir() {
-        outfile.set_file_name(tablename);
-        outfile.set_extension(extension);
+        let table: dialect::TableName = tablename.parse()?;
+        outfile.push(format!("{}.{}", table.file_stem(), extension));
     }
     Ok(outfile)
 }
@@ -460,21 +1318,46 @@ fn csv_into(
     infer: bool,
     page_size: usize,
     config: OutputConfig,
+    options: &SchemaOptions,
 ) -> Result<()> {
     let mut page: usize = 0;
-    let columns = csv_columns(csvfile, Some(tablename), false, None, None)?;
+    let table = options.table(tablename)?;
+    let headers = csv_columns(
+        csvfile,
+        Some(&table.table),
+        false,
+        None,
+        None,
+        options.dialect,
+        options.identifiers,
+    )?;
+    // Types are settled before writing so temporal values can be normalized
+    let sqltypes = if infer {
+        csv_infer(csvfile, &headers, None, None, options, None)?
+    } else {
+        vec![
+            infer::SQLType {
+                ..Default::default()
+            };
+            headers.len()
+        ]
+    };
+    let (columns, sqltypes, sources) = table_columns(&headers, &sqltypes, options);
+    let target = options.create.target(&table, options.dialect);
+    let tablename = &target.qualified(options.dialect);
     let mut rdr = csv_reader(csvfile, None, None)?;
     let mut stream = new_file(outpath, page)?;
-    let mut sqltypes: Vec<infer::SQLType> = vec![
-        infer::SQLType {
-            ..Default::default()
-        };
-        columns.len()
-    ];
+    let mut written = vec![indexed_file_path(outpath, page)];
     let mut new_page = true;
     if page_size > 0 {
         page = 1;
     }
+    // the longest field of each column, counting the header
+    let mut lengths: Vec<usize> = if config.page_header.is_some() {
+        columns.iter().map(String::len).collect()
+    } else {
+        vec![0; columns.len()]
+    };
 
     for (rounds, result) in rdr.byte_records().enumerate() {
         if page_size > 0 && rounds > 0 && (rounds % page_size) == 0 {
@@ -484,27 +1367,35 @@ fn csv_into(
             stream.flush()?;
             page += 1;
             stream = new_file(outpath, page)?;
+            written.push(indexed_file_path(outpath, page));
             new_page = true;
         }
         if new_page {
             new_page = false;
             if let Some(page_header) = config.page_header {
-                page_header(&mut stream, tablename, &columns)?;
+                page_header(&mut stream, tablename, &columns, &sqltypes)?;
             }
         } else {
             stream.write_all(&config.row_sep)?;
         }
         let row = result?;
-        for (i, (column, value)) in zip(&columns, &row).enumerate() {
-            if infer {
-                if let Some(sqltype) = infer::infer(value, sqltypes[i].index, sqltypes[i].subindex)
-                {
-                    sqltypes[i].merge(&sqltype);
-                }
-            }
+        for (i, (column, &source)) in zip(&columns, &sources).enumerate() {
+            let value = row.get(source).unwrap_or_default();
+            let raw = i > 0 && sources[i - 1] == source;
+            let converted = if infer && !raw {
+                infer::iso8601(value, &sqltypes[i])
+                    .or_else(|| infer::duration::convert(value, &sqltypes[i]))
+                    .or_else(|| (config.binary_encoder)(value, &sqltypes[i]))
+            } else {
+                None
+            };
+            let value = converted
+                .as_ref()
+                .map_or(value, |converted| converted.as_bytes());
             if i != 0 {
                 stream.write_all(&config.field_sep)?;
             }
+            lengths[i] = lengths[i].max(value.len());
             (config.field_processor)(&mut stream, column, value)?;
         }
     }
@@ -512,32 +1403,150 @@ fn csv_into(
         page_footer(&mut stream, tablename, &columns, &sqltypes)?;
     }
     stream.flush()?;
-    let schema = schema_string(&columns, &sqltypes);
-    println!(
-        "DROP TABLE IF EXISTS {0};\nCREATE TABLE {0} ({1});",
-        tablename, schema
-    );
+    let schema = if matches!(config.loader, Some(Loader::FormatFiles(_))) {
+        // bcp copies WKT as text and cannot convert it to a spatial type, so
+        // those columns hold the text, for STGeomFromText once loaded
+        let declared: Vec<infer::SQLType> = sqltypes
+            .iter()
+            .map(|sqltype| match sqltype.name {
+                infer::SQLTypeName::Geography | infer::SQLTypeName::Geometry => infer::SQLType {
+                    name: infer::SQLTypeName::Varcharmax,
+                    size: 0,
+                    ..sqltype.clone()
+                },
+                _ => sqltype.clone(),
+            })
+            .collect();
+        schema_string(&columns, &declared, options.dialect)
+    } else {
+        schema_string(&columns, &sqltypes, options.dialect)
+    };
+    let (clear, create) = options.create.statements(&target, &schema, options.dialect);
+    println!("{}{}", clear, create);
+    let described = column_descriptions(
+        csvfile,
+        &target,
+        &headers,
+        (&columns, &sqltypes, &sources),
+        None,
+        None,
+        options,
+    )?;
+    print!("{}", described);
+    match config.loader {
+        Some(Loader::Statement(load_statement)) => {
+            for path in &written {
+                println!(
+                    "{}",
+                    load_statement(
+                        path,
+                        tablename,
+                        &columns,
+                        &sqltypes,
+                        &config.field_sep,
+                        &config.row_sep
+                    )
+                );
+            }
+        }
+        Some(Loader::ControlFile(control_file)) => {
+            let control = control_file(
+                &written,
+                tablename,
+                &columns,
+                &sqltypes,
+                &config.field_sep,
+                &config.row_sep,
+            );
+            let path = outpath.with_extension("ctl");
+            fs::write(&path, control)
+                .with_context(|| format!("Failed to write control file {:?}", path))?;
+        }
+        Some(Loader::FormatFiles(format_files)) => {
+            for path in &written {
+                format_files(path, &columns, &lengths, &config.field_sep, &config.row_sep)?;
+            }
+        }
+        None => {}
+    }
     Ok(())
 }
 
-fn schema_string(columns: &[String], sqltypes: &[infer::SQLType]) -> String {
+fn schema_string(
+    columns: &[String],
+    sqltypes: &[infer::SQLType],
+    dialect: dialect::Dialect,
+) -> String {
     let mut schema = String::new();
     for (i, (column, sqlt)) in zip(columns, sqltypes).enumerate() {
         if i > 0 {
             schema.push_str(", ");
         }
-        schema.push_str(&format!("{} {}", column, sqlt));
+        schema.push_str(&format!(
+            "{} {}",
+            dialect.identifier(column),
+            dialect.type_name(sqlt)
+        ));
+        if sqlt.not_null {
+            schema.push_str(" NOT NULL");
+        }
     }
     schema
 }
 
+fn explain_string(
+    columns: &[String],
+    sqltypes: &[infer::SQLType],
+    explanations: &[infer::Explanation],
+    dialect: dialect::Dialect,
+) -> String {
+    fn shorten(value: &str) -> String {
+        if value.chars().count() > 40 {
+            format!("{}...", value.chars().take(37).collect::<String>())
+        } else {
+            value.to_string()
+        }
+    }
+    let mut explained = String::new();
+    for (column, (sqlt, explanation)) in zip(columns, zip(sqltypes, explanations)) {
+        explained.push_str(&format!("-- {} {}\n", column, dialect.type_name(sqlt)));
+        for promotion in &explanation.promotions {
+            explained.push_str(&format!(
+                "--   line {}: {:?} widened {} to {}\n",
+                promotion.line,
+                shorten(&promotion.value),
+                promotion.from,
+                promotion.to
+            ));
+        }
+        if let Some((line, value)) = &explanation.longest {
+            explained.push_str(&format!(
+                "--   longest, line {}: {:?} ({} bytes)\n",
+                line,
+                shorten(value),
+                value.len()
+            ));
+        }
+    }
+    explained
+}
+
 fn schema_string_varchar(columns: &[String], sqltypes: &[infer::SQLType]) -> String {
     let mut schema = String::new();
     for (i, (column, sqlt)) in zip(columns, sqltypes).enumerate() {
         if i > 0 {
             schema.push_str(", ");
         }
-        schema.push_str(&format!("{} {}", column, sqlt.varchar()));
+        let column = dialect::Dialect::SqlServer.identifier(column);
+        if matches!(
+            sqlt.name,
+            infer::SQLTypeName::Binary | infer::SQLTypeName::Varbinary
+        ) {
+            // OPENJSON only decodes base64 into a column declared binary
+            schema.push_str(&format!("{} VARBINARY({})", column, sqlt.size));
+        } else {
+            schema.push_str(&format!("{} {}", column, sqlt.varchar()));
+        }
     }
     schema
 }
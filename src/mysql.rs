@@ -0,0 +1,76 @@
+// Copyright 2023 Jonathan Bowman
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::dialect::Dialect;
+use crate::infer::geo::WGS84;
+use crate::infer::{SQLType, SQLTypeName};
+use std::fmt::Write;
+use std::iter::zip;
+use std::path::Path;
+
+/// A separator as a hex string literal
+fn hex_literal(bytes: &[u8]) -> String {
+    let mut literal = String::from("x'");
+    for byte in bytes {
+        let _ = write!(literal, "{:02X}", byte);
+    }
+    literal.push('\'');
+    literal
+}
+
+/// A file path as a MySQL string literal
+fn path_literal(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    format!("'{}'", path.replace('\\', "\\\\").replace('\'', "''"))
+}
+
+/// The expression storing a field read into `variable`
+fn assignment(variable: &str, sqltype: &SQLType) -> String {
+    let value = format!("NULLIF({}, '')", variable);
+    match sqltype.name {
+        SQLTypeName::Binary | SQLTypeName::Varbinary => format!("UNHEX({})", value),
+        SQLTypeName::Geography => format!(
+            "ST_GeomFromText({}, {}, 'axis-order=long-lat')",
+            value, WGS84
+        ),
+        SQLTypeName::Geometry => format!("ST_GeomFromText({})", value),
+        _ => value,
+    }
+}
+
+/// A `LOAD DATA LOCAL INFILE` statement reading one file
+pub fn load_data(
+    path: &Path,
+    tablename: &str,
+    columns: &[String],
+    sqltypes: &[SQLType],
+    field_sep: &[u8],
+    row_sep: &[u8],
+) -> String {
+    let dialect = Dialect::Mysql;
+    let variables: Vec<String> = (1..=columns.len()).map(|i| format!("@c{}", i)).collect();
+    let assignments: Vec<String> = zip(columns, zip(&variables, sqltypes))
+        .map(|(column, (variable, sqltype))| {
+            format!(
+                "{} = {}",
+                dialect.identifier(column),
+                assignment(variable, sqltype)
+            )
+        })
+        .collect();
+    format!(
+        "LOAD DATA LOCAL INFILE {}\nINTO TABLE {}\nCHARACTER SET utf8mb4\n\
+        FIELDS TERMINATED BY {} ESCAPED BY ''\nLINES TERMINATED BY {}\n\
+        IGNORE 1 LINES\n({})\nSET {};",
+        path_literal(path),
+        tablename,
+        hex_literal(field_sep),
+        hex_literal(row_sep),
+        variables.join(", "),
+        assignments.join(",\n    ")
+    )
+}
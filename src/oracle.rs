@@ -0,0 +1,100 @@
+// Copyright 2023 Jonathan Bowman
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::dialect::Dialect;
+use crate::infer::{SQLType, SQLTypeName};
+use std::fmt::Write;
+use std::iter::zip;
+use std::path::PathBuf;
+
+/// A separator as SQL*Loader writes bytes in hex
+fn hex_literal(bytes: &[u8]) -> String {
+    let mut literal = String::from("X'");
+    for byte in bytes {
+        let _ = write!(literal, "{:02X}", byte);
+    }
+    literal.push('\'');
+    literal
+}
+
+/// The mask of a timestamp as `iso8601` writes it
+fn timestamp_mask(sqltype: &SQLType, offset: bool) -> String {
+    let mut mask = String::from("YYYY-MM-DD\\\"T\\\"HH24:MI:SS");
+    if sqltype.size > 0 {
+        mask.push_str(".FF");
+    }
+    if offset {
+        mask.push_str("TZH:TZM");
+    }
+    mask
+}
+
+/// How SQL*Loader reads one field into its column
+fn field(column: &str, sqltype: &SQLType) -> String {
+    match sqltype.name {
+        SQLTypeName::Date => format!("{} DATE \"YYYY-MM-DD\"", column),
+        SQLTypeName::Datetime2 => {
+            format!(
+                "{} TIMESTAMP \"{}\"",
+                column,
+                timestamp_mask(sqltype, false)
+            )
+        }
+        SQLTypeName::Datetimeoffset => format!(
+            "{} TIMESTAMP WITH TIME ZONE \"{}\"",
+            column,
+            timestamp_mask(sqltype, true)
+        ),
+        SQLTypeName::Time => format!("{0} CHAR \"TO_DSINTERVAL('0 ' || :{0})\"", column),
+        SQLTypeName::Binary | SQLTypeName::Varbinary => {
+            format!("{} CHAR({})", column, sqltype.size.max(1) * 2)
+        }
+        SQLTypeName::Char
+        | SQLTypeName::Varchar
+        | SQLTypeName::Varcharmax
+        | SQLTypeName::Json
+        | SQLTypeName::Xml
+        | SQLTypeName::Geography
+        | SQLTypeName::Geometry
+        | SQLTypeName::Custom(_) => format!("{} CHAR({})", column, sqltype.byte_length.max(1)),
+        _ => format!("{} CHAR", column),
+    }
+}
+
+/// A SQL*Loader control file appending every data file to the table
+pub fn control_file(
+    paths: &[PathBuf],
+    tablename: &str,
+    columns: &[String],
+    sqltypes: &[SQLType],
+    field_sep: &[u8],
+    row_sep: &[u8],
+) -> String {
+    let dialect = Dialect::Oracle {
+        identifier_length: 128,
+    };
+    let mut control = String::from("LOAD DATA\nCHARACTERSET AL32UTF8\n");
+    for path in paths {
+        let _ = writeln!(
+            control,
+            "INFILE '{}' \"str {}\"",
+            path.display().to_string().replace('\'', "''"),
+            hex_literal(row_sep)
+        );
+    }
+    let fields: Vec<String> = zip(columns, sqltypes)
+        .map(|(column, sqltype)| format!("    {}", field(&dialect.identifier(column), sqltype)))
+        .collect();
+    let _ = write!(
+        control,
+        "APPEND\nINTO TABLE {}\nFIELDS TERMINATED BY {}\nTRAILING NULLCOLS\n(\n{}\n)\n",
+        tablename,
+        hex_literal(field_sep),
+        fields.join(",\n")
+    );
+    control
+}
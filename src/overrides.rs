@@ -0,0 +1,218 @@
+// Copyright 2023 Jonathan Bowman
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::infer::{self, SQLType, SQLTypeName};
+use crate::zeros::ZeroPolicy;
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::iter::zip;
+use std::path::Path;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Entry {
+    #[serde(rename = "type")]
+    name: Option<String>,
+    size: Option<usize>,
+    scale: Option<usize>,
+    nullable: Option<bool>,
+    leading_zeros: Option<ZeroPolicy>,
+}
+
+/// A column type pinned by the user
+#[derive(Clone, Debug, Default)]
+pub struct Override {
+    pub name: Option<SQLTypeName>,
+    pub size: Option<usize>,
+    pub scale: Option<usize>,
+    pub nullable: Option<bool>,
+    pub leading_zeros: Option<ZeroPolicy>,
+}
+
+/// Column type overrides, keyed by raw or sanitized column name
+#[derive(Clone, Debug, Default)]
+pub struct Overrides {
+    columns: BTreeMap<String, Override>,
+}
+
+impl Overrides {
+    /// Read overrides from a JSON file, or from TOML for any other extension
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read overrides from {:?}", path))?;
+        let entries: BTreeMap<String, Entry> = if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+        {
+            serde_json::from_str(&text)
+                .with_context(|| format!("Failed to parse overrides in {:?}", path))?
+        } else {
+            toml::from_str(&text)
+                .with_context(|| format!("Failed to parse overrides in {:?}", path))?
+        };
+        let mut columns = BTreeMap::new();
+        for (column, entry) in entries {
+            let name = match entry.name.as_deref().map(str::parse).transpose() {
+                Ok(name) => name,
+                Err(err) => bail!("Invalid override for column {}: {}", column, err),
+            };
+            columns.insert(
+                column,
+                Override {
+                    name,
+                    size: entry.size,
+                    scale: entry.scale,
+                    nullable: entry.nullable,
+                    leading_zeros: entry.leading_zeros,
+                },
+            );
+        }
+        Ok(Overrides { columns })
+    }
+
+    /// Find the override for each column, matching the raw header first
+    pub fn resolve(
+        &self,
+        raw_columns: &[String],
+        columns: &[String],
+    ) -> Result<Vec<Option<Override>>> {
+        for key in self.columns.keys() {
+            if !raw_columns.contains(key) && !columns.contains(key) {
+                bail!("Override given for unknown column {:?}", key);
+            }
+        }
+        Ok(zip(raw_columns, columns)
+            .map(|(raw, column)| {
+                self.columns
+                    .get(raw)
+                    .or_else(|| self.columns.get(column))
+                    .cloned()
+            })
+            .collect())
+    }
+}
+
+impl Override {
+    /// Fail if a value from the data cannot be stored in the pinned type
+    pub fn check(&self, value: &[u8]) -> Result<()> {
+        if value.is_empty() {
+            if self.nullable == Some(false) {
+                bail!("blank value in a column that is not nullable");
+            }
+            return Ok(());
+        }
+        let Some(target) = self.name else {
+            return Ok(());
+        };
+        let found = infer::infer_number(value, 0, 0).unwrap_or_default();
+        // Temporal values are tried with the target's own formats, since
+        // some, such as `20230101`, would be taken for numbers
+        let text = std::str::from_utf8(value).unwrap_or_default().trim();
+        let date = || infer::parse_date(text, 0).is_some();
+        let datetime = || infer::parse_datetime(text, 0).is_some();
+        let fits = match target {
+            SQLTypeName::Varcharmax => true,
+            SQLTypeName::Char | SQLTypeName::Varchar => {
+                self.size.is_none_or(|size| value.len() <= size)
+            }
+            SQLTypeName::Numeric => found.name <= target && self.numeric_fits(value),
+            SQLTypeName::Date => date(),
+            SQLTypeName::Time => infer::parse_time(text, 0).is_some(),
+            SQLTypeName::Datetime2 => date() || datetime(),
+            SQLTypeName::Datetimeoffset => {
+                date() || datetime() || infer::parse_datetimeoffset(text, 0).is_some()
+            }
+            SQLTypeName::Json | SQLTypeName::Xml | SQLTypeName::Geography => found.name == target,
+            SQLTypeName::Geometry => {
+                matches!(found.name, SQLTypeName::Geography | SQLTypeName::Geometry)
+            }
+            SQLTypeName::Binary | SQLTypeName::Varbinary => {
+                matches!(found.name, SQLTypeName::Binary | SQLTypeName::Varbinary)
+                    && self.size.is_none_or(|size| found.size <= size)
+            }
+            _ => found.name <= target,
+        };
+        if !fits {
+            bail!(
+                "{:?} does not fit {}",
+                String::from_utf8_lossy(value),
+                self.type_string(target)
+            );
+        }
+        Ok(())
+    }
+
+    fn type_string(&self, target: SQLTypeName) -> String {
+        let mut name = format!("{:?}", target).to_ascii_uppercase();
+        if let Some(size) = self.size {
+            name.push_str(&format!("({}", size));
+            if let Some(scale) = self.scale {
+                name.push_str(&format!(", {}", scale));
+            }
+            name.push(')');
+        }
+        name
+    }
+
+    fn numeric_fits(&self, value: &[u8]) -> bool {
+        let Some(precision) = self.size else {
+            return true;
+        };
+        let digits = value
+            .iter()
+            .take_while(|&&c| c != b'.')
+            .filter(|c| c.is_ascii_digit())
+            .count();
+        digits <= precision.saturating_sub(self.scale.unwrap_or(0))
+    }
+
+    /// Replace the inferred type with whatever the override pins
+    pub fn apply(&self, sqltype: &mut SQLType) {
+        if let Some(name) = self.name.filter(|&name| name != sqltype.name) {
+            let temporal = |name| {
+                matches!(
+                    name,
+                    SQLTypeName::Time | SQLTypeName::Datetime2 | SQLTypeName::Datetimeoffset
+                )
+            };
+            sqltype.size = match name {
+                SQLTypeName::Char | SQLTypeName::Varchar => sqltype.byte_length.max(1),
+                SQLTypeName::Binary | SQLTypeName::Varbinary
+                    if matches!(sqltype.name, SQLTypeName::Binary | SQLTypeName::Varbinary) =>
+                {
+                    sqltype.size
+                }
+                SQLTypeName::Binary | SQLTypeName::Varbinary => sqltype.byte_length.max(1),
+                SQLTypeName::Numeric => 18,
+                SQLTypeName::Float => 53,
+                _ if temporal(name) && temporal(sqltype.name) => sqltype.size,
+                _ if temporal(name) => 7,
+                _ => 0,
+            };
+            sqltype.name = name;
+            sqltype.scale = 0;
+            sqltype.epoch = None;
+            sqltype.duration = false;
+        }
+        if let Some(scale) = self.scale {
+            sqltype.scale = scale;
+        }
+        if let Some(size) = self.size {
+            sqltype.size = if sqltype.name == SQLTypeName::Numeric {
+                size.saturating_sub(sqltype.scale)
+            } else {
+                size
+            };
+        }
+        if let Some(nullable) = self.nullable {
+            sqltype.not_null = !nullable;
+        }
+    }
+}
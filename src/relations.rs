@@ -0,0 +1,280 @@
+// Copyright 2023 Jonathan Bowman
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::infer::{trim, SQLType, SQLTypeName};
+use crate::keys::ENTRY_OVERHEAD;
+use anyhow::Result;
+use atoi::atoi;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// One CSV file of a related set
+#[derive(Debug)]
+pub struct Table {
+    pub name: String,
+    pub path: PathBuf,
+    pub columns: Vec<String>,
+    pub sqltypes: Vec<SQLType>,
+    pub keys: Vec<usize>,
+}
+
+/// A column whose values all appear in a unique column of another table
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ForeignKey {
+    pub table: usize,
+    pub column: usize,
+    pub parent: usize,
+    pub parent_column: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct Relations {
+    pub foreign_keys: Vec<ForeignKey>,
+    /// Table indexes, each after every table it references
+    pub load_order: Vec<usize>,
+    /// Tables left out of the order by a reference cycle, in input order
+    pub cyclic: Vec<usize>,
+    /// Key columns whose values did not fit in memory and were not checked
+    pub unchecked: usize,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Family {
+    Integer,
+    Text,
+}
+
+fn family(sqltype: &SQLType) -> Option<Family> {
+    if sqltype.duration {
+        return None;
+    }
+    match sqltype.name {
+        SQLTypeName::Tinyint | SQLTypeName::Smallint | SQLTypeName::Int | SQLTypeName::Bigint => {
+            Some(Family::Integer)
+        }
+        SQLTypeName::Char | SQLTypeName::Varchar => Some(Family::Text),
+        _ => None,
+    }
+}
+
+/// A value in the form it is compared in, so that ` 7` and `07` match `7`
+fn normalize(value: &[u8], family: Family) -> Option<Vec<u8>> {
+    match family {
+        Family::Integer => {
+            let value = trim(value);
+            if value.is_empty() {
+                return None;
+            }
+            atoi::<i64>(value).map(|number| number.to_le_bytes().to_vec())
+        }
+        Family::Text if value.is_empty() => None,
+        Family::Text => Some(value.to_vec()),
+    }
+}
+
+fn singular(name: &str) -> String {
+    if let Some(stem) = name.strip_suffix("ies") {
+        format!("{}y", stem)
+    } else {
+        name.strip_suffix('s').unwrap_or(name).to_string()
+    }
+}
+
+/// How strongly a column name suggests it refers to a parent column
+fn name_score(column: &str, parent_table: &str, parent_column: &str) -> u8 {
+    let column = column.to_ascii_lowercase();
+    let parent_table = parent_table.to_ascii_lowercase();
+    let parent_column = parent_column.to_ascii_lowercase();
+    let one = singular(&parent_table);
+    let named = [&parent_table, &one].into_iter().any(|table| {
+        column == format!("{}_{}", table, parent_column)
+            || column == format!("{}{}", table, parent_column)
+    });
+    if named {
+        3
+    } else if column == parent_column && parent_column != "id" {
+        2
+    } else if column.starts_with(&one) {
+        1
+    } else {
+        0
+    }
+}
+
+/// Read the values of every key column of a table
+fn key_values(
+    table: &Table,
+    field_sep: Option<u8>,
+    row_sep: Option<u8>,
+    memory_cap: &mut usize,
+    unchecked: &mut usize,
+) -> Result<Vec<Option<HashSet<Vec<u8>>>>> {
+    let mut sets: Vec<Option<HashSet<Vec<u8>>>> = vec![None; table.columns.len()];
+    let mut used = vec![0; table.columns.len()];
+    let mut total = 0;
+    for &key in &table.keys {
+        if family(&table.sqltypes[key]).is_some() {
+            sets[key] = Some(HashSet::new());
+        }
+    }
+    let mut rdr = crate::csv_reader(&table.path, field_sep, row_sep)?;
+    let mut row = csv::ByteRecord::new();
+    while rdr.read_byte_record(&mut row)? {
+        for (i, set) in sets.iter_mut().enumerate() {
+            let Some(family) = family(&table.sqltypes[i]) else {
+                continue;
+            };
+            let Some(values) = set else {
+                continue;
+            };
+            if let Some(value) = normalize(row.get(i).unwrap_or_default(), family) {
+                let cost = value.len() + ENTRY_OVERHEAD;
+                if values.insert(value) {
+                    used[i] += cost;
+                    total += cost;
+                    if total > *memory_cap {
+                        *set = None;
+                        total -= used[i];
+                        *unchecked += 1;
+                    }
+                }
+            }
+        }
+    }
+    *memory_cap -= total;
+    Ok(sets)
+}
+
+struct Candidate {
+    column: usize,
+    parent: usize,
+    parent_column: usize,
+    contained: bool,
+    present: bool,
+}
+
+/// Tables in an order where each comes after the tables it references
+fn load_order(count: usize, foreign_keys: &[ForeignKey]) -> (Vec<usize>, Vec<usize>) {
+    let edges: HashSet<(usize, usize)> = foreign_keys
+        .iter()
+        .filter(|fk| fk.table != fk.parent)
+        .map(|fk| (fk.parent, fk.table))
+        .collect();
+    let mut order = Vec::new();
+    let mut placed = vec![false; count];
+    while let Some(next) = (0..count).find(|&table| {
+        !placed[table]
+            && edges
+                .iter()
+                .all(|&(parent, child)| child != table || placed[parent])
+    }) {
+        placed[next] = true;
+        order.push(next);
+    }
+    let cyclic = (0..count).filter(|&table| !placed[table]).collect();
+    (order, cyclic)
+}
+
+/// Find columns whose values are all found in a unique column of another table
+pub fn find_foreign_keys(
+    tables: &[Table],
+    field_sep: Option<u8>,
+    row_sep: Option<u8>,
+    memory_cap: usize,
+) -> Result<Relations> {
+    let mut relations = Relations::default();
+    let mut remaining = memory_cap;
+    let mut parents = Vec::new();
+    for table in tables {
+        parents.push(key_values(
+            table,
+            field_sep,
+            row_sep,
+            &mut remaining,
+            &mut relations.unchecked,
+        )?);
+    }
+    for (t, table) in tables.iter().enumerate() {
+        let mut candidates = Vec::new();
+        for (column, sqltype) in table.sqltypes.iter().enumerate() {
+            let Some(child_family) = family(sqltype) else {
+                continue;
+            };
+            for (p, parent) in tables.iter().enumerate() {
+                if p == t {
+                    continue;
+                }
+                for &parent_column in &parent.keys {
+                    if parents[p][parent_column].is_some()
+                        && family(&parent.sqltypes[parent_column]) == Some(child_family)
+                    {
+                        candidates.push(Candidate {
+                            column,
+                            parent: p,
+                            parent_column,
+                            contained: true,
+                            present: false,
+                        });
+                    }
+                }
+            }
+        }
+        if candidates.is_empty() {
+            continue;
+        }
+        let mut rdr = crate::csv_reader(&table.path, field_sep, row_sep)?;
+        let mut row = csv::ByteRecord::new();
+        while rdr.read_byte_record(&mut row)? {
+            for candidate in candidates.iter_mut().filter(|c| c.contained) {
+                let (Some(family), Some(values)) = (
+                    family(&table.sqltypes[candidate.column]),
+                    &parents[candidate.parent][candidate.parent_column],
+                ) else {
+                    continue;
+                };
+                let Some(value) = normalize(row.get(candidate.column).unwrap_or_default(), family)
+                else {
+                    continue;
+                };
+                candidate.present = true;
+                if !values.contains(&value) {
+                    candidate.contained = false;
+                }
+            }
+        }
+        for column in 0..table.columns.len() {
+            let best = candidates
+                .iter()
+                .filter(|c| c.column == column && c.contained && c.present)
+                .map(|c| {
+                    let parent = &tables[c.parent];
+                    let score = name_score(
+                        &table.columns[column],
+                        &parent.name,
+                        &parent.columns[c.parent_column],
+                    );
+                    (score, c)
+                })
+                .filter(|(score, _)| {
+                    *score > 0
+                        || !(table.keys.contains(&column)
+                            || family(&table.sqltypes[column]) == Some(Family::Integer))
+                })
+                .max_by_key(|(score, c)| (*score, std::cmp::Reverse((c.parent, c.parent_column))));
+            if let Some((_, c)) = best {
+                relations.foreign_keys.push(ForeignKey {
+                    table: t,
+                    column,
+                    parent: c.parent,
+                    parent_column: c.parent_column,
+                });
+            }
+        }
+    }
+    (relations.load_order, relations.cyclic) = load_order(tables.len(), &relations.foreign_keys);
+    Ok(relations)
+}
@@ -0,0 +1,126 @@
+// Copyright 2023 Jonathan Bowman
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::create::CreateStrategy;
+use crate::dialect::{Dialect, TableName};
+use crate::infer::{self, SQLType, SQLTypeName};
+use crate::SchemaOptions;
+use anyhow::{anyhow, bail, Context, Result};
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection};
+use std::iter::zip;
+use std::path::{Path, PathBuf};
+
+/// Rows inserted in each transaction
+pub const DEFAULT_BATCH: usize = 10_000;
+
+/// A CSV value as it is stored
+fn value(raw: &[u8], sqltype: &SQLType, convert: bool) -> Value {
+    if raw.is_empty() {
+        return Value::Null;
+    }
+    if convert {
+        if let Some(bytes) = infer::binary::to_bytes(raw, sqltype) {
+            return Value::Blob(bytes);
+        }
+        if let Some(text) =
+            infer::iso8601(raw, sqltype).or_else(|| infer::duration::convert(raw, sqltype))
+        {
+            return Value::Text(text);
+        }
+    }
+    let text = String::from_utf8_lossy(raw);
+    match sqltype.name {
+        SQLTypeName::Char
+        | SQLTypeName::Varchar
+        | SQLTypeName::Varcharmax
+        | SQLTypeName::Json
+        | SQLTypeName::Xml
+        | SQLTypeName::Custom(_) => Value::Text(text.into_owned()),
+        _ => Value::Text(text.trim().to_string()),
+    }
+}
+
+/// Make a table ready in a SQLite database and insert every row
+pub fn load(
+    csvfile: &PathBuf,
+    database: &Path,
+    tablename: Option<&str>,
+    batch: usize,
+    options: &SchemaOptions,
+) -> Result<(usize, String)> {
+    let dialect = Dialect::Sqlite;
+    let tablename = tablename.map_or_else(|| crate::table_name(csvfile), str::to_string);
+    let tablename = tablename
+        .parse::<TableName>()?
+        .or_schema(options.schema.as_deref());
+    tablename.validate(dialect)?;
+    let headers = crate::csv_columns(
+        csvfile,
+        Some(&tablename.table),
+        false,
+        None,
+        None,
+        dialect,
+        options.identifiers,
+    )?;
+    let sqltypes = crate::csv_infer(csvfile, &headers, None, None, options, None)?;
+    let (columns, sqltypes, sources) = crate::table_columns(&headers, &sqltypes, options);
+    if options.create == CreateStrategy::Temp {
+        bail!("A temporary table would be gone once the database is closed");
+    }
+    let target = options.create.target(&tablename, dialect);
+    let table = target.qualified(dialect);
+    let mut definitions = crate::schema_string(&columns, &sqltypes, dialect);
+    for (column, sqltype) in zip(&columns, &sqltypes) {
+        if sqltype.name == SQLTypeName::Json {
+            if let Some(check) = dialect.json_check(&dialect.identifier(column)) {
+                definitions.push_str(", ");
+                definitions.push_str(&check);
+            }
+        }
+    }
+    let mut conn = Connection::open(database)
+        .with_context(|| format!("Failed to open SQLite database {:?}", database))?;
+    let (clear, create) = options.create.statements(&target, &definitions, dialect);
+    conn.execute_batch(&format!("{}{}", clear, create))
+        .map_err(|err| anyhow!("Failed to create table {}: {}", table, err))?;
+    let insert = format!(
+        "INSERT INTO {} VALUES ({})",
+        table,
+        vec!["?"; columns.len()].join(", ")
+    );
+    let batch = batch.max(1);
+    let mut rdr = crate::csv_reader(csvfile, None, None)?;
+    let mut row = csv::ByteRecord::new();
+    let mut count = 0;
+    loop {
+        let tx = conn.transaction()?;
+        let mut rows = 0;
+        {
+            let mut statement = tx.prepare_cached(&insert)?;
+            while rows < batch && rdr.read_byte_record(&mut row)? {
+                // the raw copy of an epoch column is kept as it is
+                let values = sources.iter().enumerate().map(|(i, &source)| {
+                    let raw = i > 0 && sources[i - 1] == source;
+                    value(row.get(source).unwrap_or_default(), &sqltypes[i], !raw)
+                });
+                statement.execute(params_from_iter(values)).map_err(|err| {
+                    let line = row.position().map_or(0, |p| p.line());
+                    anyhow!("Failed to insert line {}: {}", line, err)
+                })?;
+                rows += 1;
+            }
+        }
+        tx.commit()?;
+        count += rows;
+        if rows < batch {
+            break;
+        }
+    }
+    Ok((count, table))
+}
@@ -0,0 +1,103 @@
+// Copyright 2023 Jonathan Bowman
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::infer::{trim, Registry, SQLType};
+use anyhow::bail;
+use serde::Deserialize;
+use std::str::FromStr;
+
+/// How to read a number written with leading zeros, such as `00123`
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ZeroPolicy {
+    /// as a code, like a ZIP code, kept as text so the zeros survive
+    #[default]
+    Text,
+    /// as a number, like a fixed-width amount, dropping the zeros
+    Number,
+}
+
+impl FromStr for ZeroPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &s.to_ascii_lowercase()[..] {
+            "text" => Ok(ZeroPolicy::Text),
+            "number" => Ok(ZeroPolicy::Number),
+            _ => bail!(
+                "Unknown leading zero policy {:?}, expected text or number",
+                s
+            ),
+        }
+    }
+}
+
+impl ZeroPolicy {
+    pub fn infer(
+        self,
+        checks: &Registry,
+        value: &[u8],
+        index: usize,
+        subindex: usize,
+    ) -> Option<SQLType> {
+        match self {
+            ZeroPolicy::Text => checks.infer(value, index, subindex),
+            ZeroPolicy::Number => checks.infer_number(value, index, subindex),
+        }
+    }
+}
+
+/// Whether every value of a column is digits of one width
+#[derive(Clone, Debug)]
+pub struct Padding {
+    width: Option<usize>,
+    uniform: bool,
+    padded: usize,
+    count: usize,
+}
+
+impl Default for Padding {
+    fn default() -> Self {
+        Padding {
+            width: None,
+            uniform: true,
+            padded: 0,
+            count: 0,
+        }
+    }
+}
+
+impl Padding {
+    pub fn update(&mut self, value: &[u8]) {
+        let value = trim(value);
+        if value.is_empty() || !self.uniform {
+            return;
+        }
+        if !value.iter().all(u8::is_ascii_digit)
+            || *self.width.get_or_insert(value.len()) != value.len()
+        {
+            self.uniform = false;
+            return;
+        }
+        self.count += 1;
+        if value.len() > 1 && value[0] == b'0' {
+            self.padded += 1;
+        }
+    }
+
+    /// A note for a column whose values all look zero-padded to one width
+    pub fn report(&self, column: &str) -> Option<String> {
+        let width = self.width?;
+        if !self.uniform || self.padded == 0 {
+            return None;
+        }
+        Some(format!(
+            "-- {}: all {} values are {} digits wide, {} with leading zeros",
+            column, self.count, width, self.padded
+        ))
+    }
+}
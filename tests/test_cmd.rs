@@ -7,7 +7,7 @@ mod helper;
 
 #[test]
 fn test_help_if_no_command() {
-    let mut cmd = Command::cargo_bin(assert_cmd::crate_name!()).unwrap();
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
     cmd.assert()
         .failure()
         .stderr(predicate::str::contains("help"));
@@ -15,10 +15,10 @@ fn test_help_if_no_command() {
 
 #[test]
 fn test_columns() {
-    let mut cmd = Command::cargo_bin(assert_cmd::crate_name!()).unwrap();
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
     let assert = cmd
         .arg("columns")
-        .arg(&helper::get_test_file("test_all_1.csv"))
+        .arg(helper::get_test_file("test_all_1.csv"))
         .assert();
     assert.success().stdout(
         "unused, bit, tinyint, smallint, int, \
@@ -29,24 +29,24 @@ fn test_columns() {
 
 #[test]
 fn test_schema() {
-    let mut cmd = Command::cargo_bin(assert_cmd::crate_name!()).unwrap();
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
     let assert = cmd
         .arg("schema")
         .arg("-t")
         .arg("test")
-        .arg(&helper::get_test_file("test_all_1.csv"))
+        .arg(helper::get_test_file("test_all_1.csv"))
         .assert();
     assert.success().stdout("DROP TABLE IF EXISTS test;\n\
                             CREATE TABLE test (unused BIT, bit BIT, \
                             tinyint TINYINT, smallint SMALLINT, int INT, bigint BIGINT, \
-                            decimal NUMERIC(11, 5), real FLOAT(24), float FLOAT(53), date DATE, \
+                            decimal NUMERIC(11, 5), real FLOAT(24), float CHAR(349), date DATE, \
                             time TIME(0), datetimeoffset DATETIMEOFFSET(5), datetime \
                             DATETIME2(2), char CHAR(7), varchar VARCHAR(5), varcharmax VARCHAR(MAX));\n");
 }
 
 #[test]
 fn test_output_misssing_filename() {
-    let mut cmd = Command::cargo_bin(assert_cmd::crate_name!()).unwrap();
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
     let assert = cmd
         .arg("output")
         .arg("-t")
@@ -63,7 +63,7 @@ fn test_output_misssing_filename() {
 fn test_output_infer_simple() {
     let temp = assert_fs::TempDir::new().unwrap().into_persistent();
     let output_file = temp.child("test_output.txt");
-    let mut cmd = Command::cargo_bin(assert_cmd::crate_name!()).unwrap();
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
     let _assert = cmd
         .arg("output")
         .arg("-t")
@@ -71,7 +71,7 @@ fn test_output_infer_simple() {
         //.arg("-i")
         .arg("-o")
         .arg(output_file.path())
-        .arg(&helper::get_test_file("test_all_1.csv"))
+        .arg(helper::get_test_file("test_all_1.csv"))
         .assert();
     /*
     assert.success().stdout("DROP TABLE IF EXISTS test;\n\
@@ -90,3 +90,1175 @@ fn test_output_infer_simple() {
         fs::read_to_string(output_file.path()).expect("Should have been able to read the file");
     insta::assert_debug_snapshot!(file_contents);
 }
+
+#[test]
+fn test_schema_epoch() {
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    let assert = cmd
+        .arg("schema")
+        .arg("-t")
+        .arg("test")
+        .arg("-e")
+        .arg("--keep-epoch")
+        .arg(helper::get_test_file("epoch_test.csv"))
+        .assert();
+    assert.success().stdout(
+        "DROP TABLE IF EXISTS test;\n\
+        CREATE TABLE test (id TINYINT, created_ts DATETIME2(0), created_ts_raw BIGINT, \
+        event_ms DATETIME2(3), event_ms_raw BIGINT, quantity TINYINT);\n",
+    );
+}
+
+#[test]
+fn test_output_epoch_implies_infer() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let output_file = temp.child("epoch.txt");
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("output")
+        .arg("-t")
+        .arg("test")
+        .arg("-e")
+        .arg("-o")
+        .arg(output_file.path())
+        .arg(helper::get_test_file("epoch_test.csv"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("created_ts DATETIME2(0)"));
+    output_file.assert(predicate::str::contains(
+        "1\x1F2023-11-14T22:13:20\x1F2023-11-14T22:13:20.123\x1F5",
+    ));
+}
+
+#[test]
+fn test_schema_explain() {
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    let assert = cmd
+        .arg("schema")
+        .arg("-t")
+        .arg("test")
+        .arg("--explain")
+        .arg(helper::get_test_file("simple_test.csv"))
+        .assert();
+    assert
+        .success()
+        .stdout(predicate::str::contains(
+            "-- EmailAddress VARCHAR(40)\n\
+            --   line 2: \"elfried@rmail.com\" widened BIT to CHAR(17)\n\
+            --   line 3: \"bigspender@bucks.com\" widened CHAR(17) to VARCHAR(20)\n\
+            --   longest, line 5: \"reallylongidentifier@fancydomainname.com\" (40 bytes)\n",
+        ))
+        .stdout(predicate::str::contains("DROP TABLE IF EXISTS test;\n"));
+}
+
+#[test]
+fn test_keys() {
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    let assert = cmd
+        .arg("keys")
+        .arg(helper::get_test_file("keys_test.csv"))
+        .assert();
+    assert.success().stdout(
+        "PRIMARY KEY (order_id, line)\n\
+        UNIQUE (order_id, sku)\n\
+        UNIQUE (sku, qty)\n",
+    );
+}
+
+#[test]
+fn test_keys_unchecked() {
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    let assert = cmd
+        .arg("keys")
+        .arg(helper::get_test_file("wide_keys_test.csv"))
+        .assert();
+    assert
+        .success()
+        .stdout("-- at least 500 combinations were not checked\n");
+}
+
+#[test]
+fn test_schema_keys() {
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    let assert = cmd
+        .arg("schema")
+        .arg("-t")
+        .arg("test")
+        .arg("--keys")
+        .arg(helper::get_test_file("keys_test.csv"))
+        .assert();
+    assert.success().stdout(
+        "DROP TABLE IF EXISTS test;\n\
+        CREATE TABLE test (order_id TINYINT NOT NULL, line TINYINT NOT NULL, sku CHAR(1), \
+        email CHAR(3), qty TINYINT, PRIMARY KEY (order_id, line), UNIQUE (order_id, sku), \
+        UNIQUE (sku, qty));\n",
+    );
+}
+
+#[test]
+fn test_schema_categories_check() {
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    let assert = cmd
+        .arg("schema")
+        .arg("-t")
+        .arg("test")
+        .arg("--categories")
+        .arg("check")
+        .arg(helper::get_test_file("categories_test.csv"))
+        .assert();
+    assert.success().stdout(
+        "DROP TABLE IF EXISTS test;\n\
+        CREATE TABLE test (id TINYINT, status VARCHAR(6), priority TINYINT, name CHAR(1), \
+        CHECK (status IN ('closed', 'it''s', 'open')), CHECK (priority IN (1, 2, 3)));\n",
+    );
+}
+
+#[test]
+fn test_schema_categories_lookup() {
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    let assert = cmd
+        .arg("schema")
+        .arg("-t")
+        .arg("test")
+        .arg("--categories")
+        .arg("lookup")
+        .arg("--category-limit")
+        .arg("2")
+        .arg(helper::get_test_file("categories_test.csv"))
+        .assert();
+    assert.success().stdout(
+        "DROP TABLE IF EXISTS test;\n\
+        CREATE TABLE test (id TINYINT, status VARCHAR(6), priority TINYINT, name CHAR(1));\n",
+    );
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    let assert = cmd
+        .arg("schema")
+        .arg("-t")
+        .arg("test")
+        .arg("--categories")
+        .arg("lookup")
+        .arg("--category-limit")
+        .arg("3")
+        .arg("--create")
+        .arg("truncate")
+        .arg(helper::get_test_file("categories_test.csv"))
+        .assert();
+    assert.success().stdout(
+        predicate::str::starts_with(
+            "IF OBJECT_ID(N'test_status', N'U') IS NULL\n\
+            CREATE TABLE test_status (status VARCHAR(6) NOT NULL PRIMARY KEY);\n\
+            INSERT INTO test_status (status) SELECT 'closed' \
+            WHERE NOT EXISTS (SELECT 1 FROM test_status WHERE status = 'closed');\n",
+        )
+        .and(predicate::str::contains("DROP").not())
+        .and(predicate::str::contains("TRUNCATE TABLE test_").not()),
+    );
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    let assert = cmd
+        .arg("schema")
+        .arg("-t")
+        .arg("test")
+        .arg("--categories")
+        .arg("lookup")
+        .arg("--category-limit")
+        .arg("3")
+        .arg("--dialect")
+        .arg("oracle")
+        .arg(helper::get_test_file("categories_test.csv"))
+        .assert();
+    assert.success().stdout(predicate::str::contains(
+        "INSERT INTO test_priority (priority) VALUES (1);\n\
+        INSERT INTO test_priority (priority) VALUES (2);\n\
+        INSERT INTO test_priority (priority) VALUES (3);\n",
+    ));
+}
+
+#[test]
+fn test_relations_quoted() {
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    let assert = cmd
+        .arg("relations")
+        .arg(helper::get_test_file("relations_quoted"))
+        .assert();
+    assert.success().stdout(
+        "-- load order: order, 2023_lines\n\
+        ALTER TABLE [2023_lines] ADD FOREIGN KEY (order_id) REFERENCES [order] (id);\n",
+    );
+}
+
+#[test]
+fn test_relations() {
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    let assert = cmd
+        .arg("relations")
+        .arg(helper::get_test_file("relations"))
+        .assert();
+    assert.success().stdout(
+        "-- load order: customers, orders, products, order_lines\n\
+        ALTER TABLE orders ADD FOREIGN KEY (customer_id) REFERENCES customers (id);\n\
+        ALTER TABLE order_lines ADD FOREIGN KEY (order_id) REFERENCES orders (id);\n\
+        ALTER TABLE order_lines ADD FOREIGN KEY (sku) REFERENCES products (sku);\n",
+    );
+}
+
+#[test]
+fn test_schema_out_of_range_varchar() {
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    let assert = cmd
+        .arg("schema")
+        .arg("-t")
+        .arg("test")
+        .arg("--out-of-range")
+        .arg("varchar")
+        .arg(helper::get_test_file("range_test.csv"))
+        .assert();
+    assert.success().stdout(
+        "DROP TABLE IF EXISTS test;\n\
+        CREATE TABLE test (id TINYINT, big VARCHAR(20), huge VARCHAR(40), born CHAR(10), \
+        ratio CHAR(5));\n",
+    );
+}
+
+#[test]
+fn test_padding() {
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    let assert = cmd
+        .arg("padding")
+        .arg(helper::get_test_file("zeros_test.csv"))
+        .assert();
+    assert.success().stdout(
+        "-- zip: all 3 values are 5 digits wide, 2 with leading zeros\n\
+        -- amount: all 3 values are 6 digits wide, 3 with leading zeros\n",
+    );
+}
+
+#[test]
+fn test_schema_leading_zeros_override() {
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    let assert = cmd
+        .arg("schema")
+        .arg("-t")
+        .arg("test")
+        .arg("--overrides")
+        .arg(helper::get_test_file("zeros_overrides.toml"))
+        .arg(helper::get_test_file("zeros_test.csv"))
+        .assert();
+    assert.success().stdout(
+        "DROP TABLE IF EXISTS test;\n\
+        CREATE TABLE test (zip CHAR(5), amount SMALLINT, id TINYINT);\n",
+    );
+}
+
+#[test]
+fn test_schema_documents() {
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    let assert = cmd
+        .arg("schema")
+        .arg("-t")
+        .arg("test")
+        .arg(helper::get_test_file("documents_test.csv"))
+        .assert();
+    assert.success().stdout(
+        "DROP TABLE IF EXISTS test;\n\
+        CREATE TABLE test (id TINYINT, payload NVARCHAR(MAX), markup XML, mixed VARCHAR(11), \
+        CHECK (ISJSON(payload) = 1));\n",
+    );
+}
+
+#[test]
+fn test_output_binary_as_hex() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let output_file = temp.child("binary.txt");
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    let assert = cmd
+        .arg("output")
+        .arg("-t")
+        .arg("test")
+        .arg("-i")
+        .arg("-o")
+        .arg(output_file.path())
+        .arg(helper::get_test_file("binary_test.csv"))
+        .assert();
+    assert.success().stdout(
+        "DROP TABLE IF EXISTS test;\n\
+        CREATE TABLE test (id TINYINT, digest BINARY(16), blob VARBINARY(5), flag BINARY(1), \
+        mixed VARCHAR(20));\n",
+    );
+    let file_contents = fs::read_to_string(output_file.path()).unwrap();
+    assert!(file_contents.contains("\x1F48656C6C6F\x1F01\x1F0x0102\x1E"));
+}
+
+#[test]
+fn test_save_merge_create() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let states = [temp.child("a.json"), temp.child("b.json")];
+    for (csvfile, state) in ["state_a.csv", "state_b.csv"].iter().zip(&states) {
+        let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+        cmd.arg("save")
+            .arg("-o")
+            .arg(state.path())
+            .arg(helper::get_test_file(csvfile))
+            .assert()
+            .success();
+    }
+    let merged = temp.child("merged.json");
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("merge")
+        .arg("-o")
+        .arg(merged.path())
+        .arg(states[0].path())
+        .arg(states[1].path())
+        .assert()
+        .success();
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    let assert = cmd
+        .arg("create")
+        .arg("-t")
+        .arg("test")
+        .arg(merged.path())
+        .assert();
+    assert.success().stdout(
+        "DROP TABLE IF EXISTS test;\n\
+        CREATE TABLE test (id SMALLINT, amount NUMERIC(7, 3), code VARCHAR(4), seen DATE);\n",
+    );
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    let assert = cmd
+        .arg("create")
+        .arg("-t")
+        .arg("test")
+        .arg("--dialect")
+        .arg("postgres")
+        .arg("--create")
+        .arg("if-not-exists")
+        .arg("--schema")
+        .arg("sales")
+        .arg(merged.path())
+        .assert();
+    assert.success().stdout(
+        "CREATE TABLE IF NOT EXISTS sales.test \
+        (id smallint, amount numeric(7, 3), code varchar(4), seen date);\n",
+    );
+}
+
+#[test]
+fn test_schema_points() {
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    let assert = cmd
+        .arg("schema")
+        .arg("-t")
+        .arg("test")
+        .arg("--points")
+        .arg(helper::get_test_file("geo_test.csv"))
+        .assert();
+    assert.success().stdout(
+        "DROP TABLE IF EXISTS test;\n\
+        CREATE TABLE test (id TINYINT, pickup_lat NUMERIC(6, 4), pickup_lon NUMERIC(7, 4), \
+        shape GEOGRAPHY, plane GEOMETRY, \
+        pickup_point AS geography::Point(pickup_lat, pickup_lon, 4326));\n",
+    );
+}
+
+#[test]
+fn test_output_json_spatial() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let output_file = temp.child("geo.sql");
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("output")
+        .arg("-t")
+        .arg("test")
+        .arg("-j")
+        .arg("-o")
+        .arg(output_file.path())
+        .arg(helper::get_test_file("geo_test.csv"))
+        .assert()
+        .success();
+    let file_contents = fs::read_to_string(output_file.path()).unwrap();
+    assert!(file_contents.contains("    geography::STGeomFromText(shape, 4326),\n"));
+    assert!(file_contents.contains("    geometry::STGeomFromText(plane, 0)\n"));
+}
+
+#[test]
+fn test_output_bcp_spatial_as_text() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let output_file = temp.child("geo.txt");
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("output")
+        .arg("-t")
+        .arg("test")
+        .arg("-i")
+        .arg("-o")
+        .arg(output_file.path())
+        .arg(helper::get_test_file("geo_test.csv"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "shape VARCHAR(MAX), plane VARCHAR(MAX));",
+        ));
+    output_file.assert(predicate::str::contains("\x1FPOINT(-74.006 40.7128)\x1F"));
+}
+
+#[test]
+fn test_output_durations_as_time() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let output_file = temp.child("durations.txt");
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    let assert = cmd
+        .arg("output")
+        .arg("-t")
+        .arg("test")
+        .arg("-i")
+        .arg("--durations")
+        .arg("time")
+        .arg("-o")
+        .arg(output_file.path())
+        .arg(helper::get_test_file("durations_test.csv"))
+        .assert();
+    assert.success().stdout(
+        "DROP TABLE IF EXISTS test;\n\
+        CREATE TABLE test (id TINYINT, elapsed BIGINT, trip TIME(1), wait TIME(0));\n",
+    );
+    let file_contents = fs::read_to_string(output_file.path()).unwrap();
+    assert!(file_contents.contains("\x1E2\x1F273600\x1F00:45:00.0\x1F02:00:00\x1E"));
+}
+
+#[test]
+fn test_output_duration_override() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let output_file = temp.child("durations.txt");
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    let assert = cmd
+        .arg("output")
+        .arg("-t")
+        .arg("test")
+        .arg("--overrides")
+        .arg(helper::get_test_file("durations_overrides.toml"))
+        .arg("-o")
+        .arg(output_file.path())
+        .arg(helper::get_test_file("durations_test.csv"))
+        .assert();
+    assert
+        .success()
+        .stdout(predicate::str::contains("wait VARCHAR(5));"));
+    let file_contents = fs::read_to_string(output_file.path()).unwrap();
+    assert!(file_contents.contains("\x1E1\x1F451800\x1F330.5\x1F00:10\x1E"));
+    assert!(file_contents.contains("\x1F2700.0\x1FPT2H\x1E"));
+}
+
+#[test]
+fn test_schema_durations_as_seconds() {
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    let assert = cmd
+        .arg("schema")
+        .arg("-t")
+        .arg("test")
+        .arg(helper::get_test_file("durations_test.csv"))
+        .assert();
+    assert.success().stdout(
+        "DROP TABLE IF EXISTS test;\n\
+        CREATE TABLE test (id TINYINT, elapsed BIGINT, trip NUMERIC(19, 1), wait BIGINT);\n",
+    );
+}
+
+#[test]
+fn test_schema_postgres() {
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    let assert = cmd
+        .arg("schema")
+        .arg("-t")
+        .arg("test")
+        .arg("-k")
+        .arg("--dialect")
+        .arg("postgres")
+        .arg(helper::get_test_file("postgres_test.csv"))
+        .assert();
+    assert.success().stdout(
+        "DROP TABLE IF EXISTS test;\n\
+        CREATE TABLE test (id smallint NOT NULL, \"Flag\" boolean, amount numeric(4, 2), \
+        t_when date, stamp timestamptz(1), payload jsonb, digest bytea, note varchar(8), \
+        PRIMARY KEY (id), UNIQUE (t_when));\n",
+    );
+}
+
+#[test]
+fn test_output_postgres_copy() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let output_file = temp.child("postgres.txt");
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("output")
+        .arg("-t")
+        .arg("test")
+        .arg("--dialect")
+        .arg("postgres")
+        .arg("-o")
+        .arg(output_file.path())
+        .arg(helper::get_test_file("postgres_test.csv"))
+        .assert()
+        .success();
+    let file_contents = fs::read_to_string(output_file.path()).unwrap();
+    assert_eq!(
+        file_contents,
+        "1\t1\t3.5\t2023-01-05\t2023-01-05T10:00:00.0+02:00\t{\"a\": 1}\t\\\\x48656c6c6f\tit's\n\
+        2\t0\t12.25\t2023-02-11\t2023-02-11T11:30:00.5+00:00\t[1]\t\\\\x01\ttab\\there\n\
+        3\t\\N\t\\N\t2023-03-01\t\\N\t\\N\t\\N\t\\N"
+    );
+}
+
+#[test]
+fn test_output_mysql_load_data() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let output_file = temp.child("mysql.txt");
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("output")
+        .arg("-t")
+        .arg("test")
+        .arg("--dialect")
+        .arg("mysql")
+        .arg("-o")
+        .arg(output_file.path())
+        .arg(helper::get_test_file("postgres_test.csv"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "CREATE TABLE test (id TINYINT UNSIGNED, Flag TINYINT(1), amount DECIMAL(4, 2), \
+            t_when DATE, stamp DATETIME(1), payload JSON, digest VARBINARY(5), note VARCHAR(8));",
+        ))
+        .stdout(predicate::str::contains(format!(
+            "LOAD DATA LOCAL INFILE '{}'\nINTO TABLE test\n",
+            output_file.path().display()
+        )))
+        .stdout(predicate::str::contains(
+            "FIELDS TERMINATED BY x'1F' ESCAPED BY ''\nLINES TERMINATED BY x'1E'\nIGNORE 1 LINES\n",
+        ))
+        .stdout(predicate::str::contains(
+            "    digest = UNHEX(NULLIF(@c7, '')),\n    note = NULLIF(@c8, '');",
+        ));
+    let file_contents = fs::read_to_string(output_file.path()).unwrap();
+    assert_eq!(
+        file_contents,
+        "id\x1FFlag\x1Famount\x1Ft_when\x1Fstamp\x1Fpayload\x1Fdigest\x1Fnote\x1E\
+        1\x1F1\x1F3.5\x1F2023-01-05\x1F2023-01-05T10:00:00.0+02:00\x1F{\"a\": 1}\x1F48656C6C6F\x1Fit's\x1E\
+        2\x1F0\x1F12.25\x1F2023-02-11\x1F2023-02-11T11:30:00.5+00:00\x1F[1]\x1F01\x1Ftab\there\x1E\
+        3\x1F\x1F\x1F2023-03-01\x1F\x1F\x1F\x1F"
+    );
+}
+
+#[test]
+fn test_output_oracle_control_file() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let output_file = temp.child("oracle.txt");
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("output")
+        .arg("-t")
+        .arg("test")
+        .arg("--dialect")
+        .arg("oracle")
+        .arg("-o")
+        .arg(output_file.path())
+        .arg(helper::get_test_file("postgres_test.csv"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "CREATE TABLE test (id NUMBER(3), Flag NUMBER(1), amount NUMBER(4, 2), when DATE, \
+            stamp TIMESTAMP(1) WITH TIME ZONE, payload CLOB, digest RAW(5), \
+            note VARCHAR2(8 CHAR));",
+        ));
+    let file_contents = fs::read_to_string(output_file.path()).unwrap();
+    assert!(file_contents.starts_with(
+        "1\x1F1\x1F3.5\x1F2023-01-05\x1F2023-01-05T10:00:00.0+02:00\x1F{\"a\": 1}\x1F48656C6C6F\x1Fit's\x1E"
+    ));
+    let control = fs::read_to_string(temp.child("oracle.ctl").path()).unwrap();
+    assert_eq!(
+        control,
+        format!(
+            "LOAD DATA\nCHARACTERSET AL32UTF8\nINFILE '{}' \"str X'1E'\"\nAPPEND\n\
+            INTO TABLE test\nFIELDS TERMINATED BY X'1F'\nTRAILING NULLCOLS\n(\n    \
+            id CHAR,\n    \
+            Flag CHAR,\n    \
+            amount CHAR,\n    \
+            when DATE \"YYYY-MM-DD\",\n    \
+            stamp TIMESTAMP WITH TIME ZONE \"YYYY-MM-DD\\\"T\\\"HH24:MI:SS.FFTZH:TZM\",\n    \
+            payload CHAR(8),\n    \
+            digest CHAR(10),\n    \
+            note CHAR(8)\n)\n",
+            output_file.path().display()
+        )
+    );
+}
+
+#[test]
+fn test_output_oracle_control_file_quote() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let output_file = temp.child("o'brien.txt");
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("output")
+        .arg("-t")
+        .arg("test")
+        .arg("--dialect")
+        .arg("oracle")
+        .arg("-o")
+        .arg(output_file.path())
+        .arg(helper::get_test_file("postgres_test.csv"))
+        .assert()
+        .success();
+    let control = fs::read_to_string(temp.child("o'brien.ctl").path()).unwrap();
+    assert!(control.contains(&format!(
+        "INFILE '{}' ",
+        temp.child("o''brien.txt").path().display()
+    )));
+}
+
+#[test]
+fn test_columns_oracle_identifier_length() {
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("columns")
+        .arg("--dialect")
+        .arg("oracle11")
+        .arg(helper::get_test_file("long_names_test.csv"))
+        .assert()
+        .success()
+        .stdout("customer_mailing_address_line_, customer_mailing_address_lin_2, zip\n");
+}
+
+#[test]
+fn test_columns_identifiers_suffix() {
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("columns")
+        .arg("-t")
+        .arg("test")
+        .arg("--identifiers")
+        .arg("suffix")
+        .arg(helper::get_test_file("postgres_test.csv"))
+        .assert()
+        .success()
+        .stdout("id, Flag, amount, when_t, stamp, payload, digest, note\n");
+}
+
+#[test]
+fn test_identifiers_quote() {
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("schema")
+        .arg("-t")
+        .arg("test")
+        .arg("--identifiers")
+        .arg("quote")
+        .arg(helper::get_test_file("postgres_test.csv"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "amount NUMERIC(4, 2), [when] DATE, stamp",
+        ));
+    let temp = assert_fs::TempDir::new().unwrap();
+    let output_file = temp.child("quoted.sql");
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("output")
+        .arg("-t")
+        .arg("test")
+        .arg("-j")
+        .arg("--identifiers")
+        .arg("quote")
+        .arg("-o")
+        .arg(output_file.path())
+        .arg(helper::get_test_file("postgres_test.csv"))
+        .assert()
+        .success();
+    let file_contents = fs::read_to_string(output_file.path()).unwrap();
+    assert!(file_contents.contains("    amount,\n    [when],\n    stamp,\n"));
+    assert!(file_contents.contains("\"when\": \"2023-01-05\""));
+    assert!(file_contents.contains(", [when] VARCHAR(10), "));
+    let output_file = temp.child("quoted.txt");
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("output")
+        .arg("-t")
+        .arg("test")
+        .arg("-o")
+        .arg(output_file.path())
+        .arg(helper::get_test_file("postgres_test.csv"))
+        .assert()
+        .success();
+    let file_contents = fs::read_to_string(output_file.path()).unwrap();
+    assert!(file_contents.starts_with(
+        "id\x1FFlag\x1Famount\x1Ft_when\x1Fstamp\x1Fpayload\x1Fdigest\x1Fnote\x1E1\x1F"
+    ));
+}
+
+#[test]
+fn test_schema_qualified_table() {
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("schema")
+        .arg("-t")
+        .arg("sales.[order lines]")
+        .arg(helper::get_test_file("state_a.csv"))
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "DROP TABLE IF EXISTS sales.[order lines];\nCREATE TABLE sales.[order lines] (",
+        ));
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("schema")
+        .arg("-t")
+        .arg("Orders")
+        .arg("--schema")
+        .arg("sales")
+        .arg("--dialect")
+        .arg("postgres")
+        .arg(helper::get_test_file("state_a.csv"))
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "DROP TABLE IF EXISTS sales.\"Orders\";\n",
+        ));
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("schema")
+        .arg("-t")
+        .arg("orders; DROP TABLE users")
+        .arg(helper::get_test_file("state_a.csv"))
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "DROP TABLE IF EXISTS [orders; DROP TABLE users];\n",
+        ));
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("schema")
+        .arg("-t")
+        .arg("warehouse.sales.orders")
+        .arg("--dialect")
+        .arg("mysql")
+        .arg(helper::get_test_file("state_a.csv"))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("has more than 2 parts"));
+}
+
+#[test]
+fn test_output_qualified_table_file() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("output")
+        .arg("-t")
+        .arg("sales.[order lines]")
+        .arg("-j")
+        .arg("-p")
+        .arg("1")
+        .arg("-o")
+        .arg(temp.path())
+        .arg(helper::get_test_file("state_a.csv"))
+        .assert()
+        .success();
+    let file_contents = fs::read_to_string(temp.child("sales.order_lines.sql").path()).unwrap();
+    assert!(file_contents.starts_with("INSERT INTO sales.[order lines]\n"));
+    temp.child("sales.order_lines_2.sql")
+        .assert(predicate::path::exists());
+}
+
+#[test]
+fn test_output_postgres_json() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let output_file = temp.child("postgres.sql");
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("output")
+        .arg("-t")
+        .arg("test")
+        .arg("-j")
+        .arg("--dialect")
+        .arg("postgres")
+        .arg("-o")
+        .arg(output_file.path())
+        .arg(helper::get_test_file("postgres_test.csv"))
+        .assert()
+        .success();
+    let file_contents = fs::read_to_string(output_file.path()).unwrap();
+    assert!(file_contents.starts_with("INSERT INTO test\nSELECT\n    id,\n    \"Flag\","));
+    assert!(file_contents.contains("    payload::jsonb,\n"));
+    assert!(file_contents.contains("\nFROM json_to_recordset('[\n    {\"id\": \"1\""));
+    assert!(file_contents.ends_with(
+        "]') AS x(id smallint, \"Flag\" boolean, amount numeric(4, 2), t_when date, \
+        stamp timestamptz(1), payload text, digest bytea, note varchar(8));\n"
+    ));
+}
+
+#[test]
+fn test_load_sqlite() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let database = temp.child("test.sqlite");
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("load")
+        .arg("--sqlite")
+        .arg(database.path())
+        .arg("-t")
+        .arg("test")
+        .arg("--batch")
+        .arg("2")
+        .arg(helper::get_test_file("postgres_test.csv"))
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("Loaded 3 rows into"));
+    let conn = rusqlite::Connection::open(database.path()).unwrap();
+    let sql: String = conn
+        .query_row(
+            "SELECT sql FROM sqlite_master WHERE name = 'test'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(
+        sql,
+        "CREATE TABLE test (id INTEGER, Flag INTEGER, amount NUMERIC, t_when TEXT, stamp TEXT, \
+        payload TEXT, digest BLOB, note TEXT, CHECK (json_valid(payload)))"
+    );
+    let (amount, digest, note): (f64, Vec<u8>, String) = conn
+        .query_row(
+            "SELECT amount, digest, note FROM test WHERE id = 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(
+        (amount, &digest[..], &note[..]),
+        (3.5, &b"Hello"[..], "it's")
+    );
+    let nulls: i64 = conn
+        .query_row(
+            "SELECT count(*) FROM test WHERE amount IS NULL",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(nulls, 1);
+}
+
+#[test]
+fn test_schema_create_strategies() {
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("schema")
+        .arg("-t")
+        .arg("sales.orders")
+        .arg("--create")
+        .arg("truncate")
+        .arg(helper::get_test_file("state_a.csv"))
+        .assert()
+        .success()
+        .stdout(
+            "IF OBJECT_ID(N'sales.orders', N'U') IS NULL\n\
+            CREATE TABLE sales.orders (id TINYINT, amount NUMERIC(4, 2), code CHAR(2), seen DATE);\n\
+            TRUNCATE TABLE sales.orders;\n",
+        );
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("schema")
+        .arg("-t")
+        .arg("sales.orders")
+        .arg("--create")
+        .arg("staging")
+        .arg(helper::get_test_file("state_a.csv"))
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::is_match("^CREATE TABLE sales.orders_staging_[0-9]{14} \\(").unwrap(),
+        );
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("schema")
+        .arg("-t")
+        .arg("sales.orders")
+        .arg("--create")
+        .arg("temp")
+        .arg(helper::get_test_file("state_a.csv"))
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("CREATE TABLE #orders ("));
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("schema")
+        .arg("-t")
+        .arg("orders")
+        .arg("--create")
+        .arg("if-not-exists")
+        .arg("--dialect")
+        .arg("postgres")
+        .arg(helper::get_test_file("state_a.csv"))
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "CREATE TABLE IF NOT EXISTS orders (",
+        ));
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("schema")
+        .arg("-t")
+        .arg("orders")
+        .arg("--dialect")
+        .arg("oracle")
+        .arg(helper::get_test_file("state_a.csv"))
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "BEGIN\n  EXECUTE IMMEDIATE 'DROP TABLE orders';\nEXCEPTION\n  WHEN OTHERS THEN\n    \
+            IF SQLCODE != -942 THEN\n      RAISE;\n    END IF;\nEND;\n/\nCREATE TABLE orders (",
+        ));
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("schema")
+        .arg("-t")
+        .arg("orders")
+        .arg("--create")
+        .arg("truncate")
+        .arg("--dialect")
+        .arg("oracle")
+        .arg(helper::get_test_file("state_a.csv"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("IF SQLCODE != -955 THEN").and(
+            predicate::str::ends_with("END;\n/\nTRUNCATE TABLE orders;\n"),
+        ));
+}
+
+#[test]
+fn test_output_bcp_create() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("output")
+        .arg("-t")
+        .arg("orders")
+        .arg("--create")
+        .arg("temp")
+        .arg("-o")
+        .arg(temp.child("orders.txt").path())
+        .arg(helper::get_test_file("state_a.csv"))
+        .assert()
+        .failure()
+        .stderr("bcp cannot fill a temporary table, so use --json with --create temp\n");
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("output")
+        .arg("-t")
+        .arg("sales.orders")
+        .arg("--create")
+        .arg("staging")
+        .arg("-o")
+        .arg(temp.child("staged.txt").path())
+        .arg(helper::get_test_file("state_a.csv"))
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::is_match("^CREATE TABLE sales.orders_staging_[0-9]{14} \\(").unwrap(),
+        );
+}
+
+#[test]
+fn test_load_sqlite_truncate() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let database = temp.child("test.sqlite");
+    for _ in 0..2 {
+        let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+        cmd.arg("load")
+            .arg("--sqlite")
+            .arg(database.path())
+            .arg("--create")
+            .arg("truncate")
+            .arg(helper::get_test_file("state_a.csv"))
+            .assert()
+            .success()
+            .stdout(predicate::str::starts_with("Loaded 2 rows into state_a in"));
+    }
+    let conn = rusqlite::Connection::open(database.path()).unwrap();
+    let rows: i64 = conn
+        .query_row("SELECT COUNT(*) FROM state_a", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(rows, 2);
+}
+
+#[test]
+fn test_diff_table_definition() {
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("diff")
+        .arg(helper::get_test_file("diff_test.csv"))
+        .arg(helper::get_test_file("diff_table.sql"))
+        .assert()
+        .success()
+        .stdout(
+            "ALTER TABLE sales.orders ALTER COLUMN id SMALLINT NOT NULL;\n\
+            ALTER TABLE sales.orders ALTER COLUMN amount NUMERIC(7, 3);\n\
+            ALTER TABLE sales.orders ALTER COLUMN code VARCHAR(4);\n\
+            -- seen DATE cannot hold the data, which needs CHAR(10), without converting its rows\n\
+            -- ALTER TABLE sales.orders ALTER COLUMN seen CHAR(10);\n\
+            -- status VARCHAR(20) is wider than the data needs, which fits BIT\n\
+            ALTER TABLE sales.orders ADD note CHAR(5);\n\
+            -- region is in the table but not in the file\n",
+        );
+}
+
+#[test]
+fn test_diff_quoted_names() {
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("diff")
+        .arg(helper::get_test_file("diff_test.csv"))
+        .arg(helper::get_test_file("diff_quoted_table.sql"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "ALTER TABLE sales.orders ALTER COLUMN amount NUMERIC(7, 3);\n",
+        ))
+        .stdout(predicate::str::contains(
+            "-- [odd]](x] is in the table but not in the file\n\
+            -- [say \"hi\", (x] is in the table but not in the file\n",
+        ));
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("diff")
+        .arg("--dialect")
+        .arg("postgres")
+        .arg(helper::get_test_file("diff_test.csv"))
+        .arg(helper::get_test_file("diff_table.sql"))
+        .assert()
+        .failure()
+        .stderr("Tables can only be compared with SQL Server definitions for now\n");
+}
+
+#[test]
+fn test_diff_national_types() {
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("diff")
+        .arg(helper::get_test_file("diff_test.csv"))
+        .arg(helper::get_test_file("diff_national_table.sql"))
+        .assert()
+        .success()
+        .stdout(
+            "-- id INT is wider than the data needs, which fits SMALLINT\n\
+            ALTER TABLE sales.orders ALTER COLUMN code NVARCHAR(4);\n\
+            -- seen DATETIME cannot hold the data, which needs CHAR(10), without converting its rows\n\
+            -- ALTER TABLE sales.orders ALTER COLUMN seen CHAR(10);\n\
+            -- status NVARCHAR(MAX) is wider than the data needs, which fits BIT\n",
+        );
+}
+
+#[test]
+fn test_diff_state_file() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let state = temp.child("a.json");
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("save")
+        .arg("-o")
+        .arg(state.path())
+        .arg(helper::get_test_file("state_a.csv"))
+        .assert()
+        .success();
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("diff")
+        .arg("-t")
+        .arg("orders")
+        .arg(helper::get_test_file("state_b.csv"))
+        .arg(state.path())
+        .assert()
+        .success()
+        .stdout(
+            "ALTER TABLE orders ALTER COLUMN id SMALLINT;\n\
+            ALTER TABLE orders ALTER COLUMN amount NUMERIC(7, 3);\n\
+            ALTER TABLE orders ALTER COLUMN code VARCHAR(4);\n",
+        );
+}
+
+#[test]
+fn test_schema_descriptions() {
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("schema")
+        .arg("-t")
+        .arg("sales.orders")
+        .arg("--describe")
+        .arg(helper::get_test_file("descriptions_test.csv"))
+        .assert()
+        .success()
+        .stdout(predicate::str::ends_with(
+            "EXEC sp_addextendedproperty @name = N'MS_Description', \
+            @value = N'Customer Name (Legal)', @level0type = N'SCHEMA', @level0name = N'sales', \
+            @level1type = N'TABLE', @level1name = N'orders', \
+            @level2type = N'COLUMN', @level2name = N'Customer_Name__Legal_';\n\
+            EXEC sp_addextendedproperty @name = N'MS_Description', \
+            @value = N'Order Total $', @level0type = N'SCHEMA', @level0name = N'sales', \
+            @level1type = N'TABLE', @level1name = N'orders', \
+            @level2type = N'COLUMN', @level2name = N'Order_Total__';\n",
+        ));
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("schema")
+        .arg("-t")
+        .arg("orders")
+        .arg("--dialect")
+        .arg("postgres")
+        .arg("--descriptions")
+        .arg(helper::get_test_file("descriptions.toml"))
+        .arg(helper::get_test_file("descriptions_test.csv"))
+        .assert()
+        .success()
+        .stdout(predicate::str::ends_with(
+            "COMMENT ON COLUMN orders.\"Customer_Name__Legal_\" IS 'Customer Name (Legal)';\n\
+            COMMENT ON COLUMN orders.id IS 'Order number';\n\
+            COMMENT ON COLUMN orders.\"Order_Total__\" IS 'Total in US dollars, with tax';\n",
+        ));
+    let temp = assert_fs::TempDir::new().unwrap();
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("output")
+        .arg("-t")
+        .arg("sales.orders")
+        .arg("--describe")
+        .arg("-o")
+        .arg(temp.child("orders.txt").path())
+        .arg(helper::get_test_file("descriptions_test.csv"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "@value = N'Order Total $', @level0type = N'SCHEMA', @level0name = N'sales', \
+            @level1type = N'TABLE', @level1name = N'orders', \
+            @level2type = N'COLUMN', @level2name = N'Order_Total__';\n",
+        ));
+}
+
+#[test]
+fn test_output_bcp_format_files() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let mut cmd = Command::cargo_bin(assert_cmd::pkg_name!()).unwrap();
+    cmd.arg("output")
+        .arg("-t")
+        .arg("orders")
+        .arg("-i")
+        .arg("-p")
+        .arg("1")
+        .arg("-o")
+        .arg(temp.path())
+        .arg(helper::get_test_file("state_a.csv"))
+        .assert()
+        .success();
+    for page in ["orders", "orders_2"] {
+        temp.child(format!("{}.fmt", page)).assert(
+            "14.0\n4\n\
+            1\tSQLCHAR\t0\t2\t\"\x1F\"\t1\tid\t\"\"\n\
+            2\tSQLCHAR\t0\t6\t\"\x1F\"\t2\tamount\t\"\"\n\
+            3\tSQLCHAR\t0\t4\t\"\x1F\"\t3\tcode\t\"\"\n\
+            4\tSQLCHAR\t0\t10\t\"\x1E\"\t4\tseen\t\"\"\n",
+        );
+        temp.child(format!("{}.xml", page))
+            .assert(predicate::path::missing());
+    }
+    let xml = busser::bcp::xml_format_file(
+        &["id".to_string(), "seen".to_string()],
+        &[2, 10],
+        b"\t",
+        b"\r\n",
+    )
+    .unwrap();
+    let doc = roxmltree::Document::parse(&xml).unwrap();
+    let fields: Vec<_> = doc
+        .descendants()
+        .filter(|node| node.has_tag_name("FIELD"))
+        .map(|node| {
+            (
+                node.attribute("TERMINATOR").unwrap().to_string(),
+                node.attribute("MAX_LENGTH").unwrap().to_string(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        fields,
+        [
+            ("\t".to_string(), "2".to_string()),
+            ("\r\n".to_string(), "10".to_string())
+        ]
+    );
+    let columns: Vec<_> = doc
+        .descendants()
+        .filter(|node| node.has_tag_name("COLUMN"))
+        .map(|node| node.attribute("NAME").unwrap())
+        .collect();
+    assert_eq!(columns, ["id", "seen"]);
+    assert!(busser::bcp::xml_format_file(&["id".to_string()], &[2], b"\x1F", b"\x1E").is_err());
+}
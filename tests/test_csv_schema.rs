@@ -1,14 +1,113 @@
+use busser::infer::{SQLType, SQLTypeName, TypeCheck};
 mod helper;
 
 #[test]
 fn test_schema_returns() {
-    let table = busser::csv_schema(&helper::get_test_file("test_all_1.csv"), "test", false)
-        .unwrap();
+    let table = busser::csv_schema(
+        &helper::get_test_file("test_all_1.csv"),
+        "test",
+        false,
+        &Default::default(),
+    )
+    .unwrap();
     assert_eq!(
         table,
         "DROP TABLE IF EXISTS test;\nCREATE TABLE test (unused BIT, bit BIT, tinyint \
         TINYINT, smallint SMALLINT, int INT, bigint BIGINT, decimal NUMERIC(11, 5), real FLOAT(24), \
-        float FLOAT(53), date DATE, time TIME(0), datetimeoffset DATETIMEOFFSET(5), datetime \
+        float CHAR(349), date DATE, time TIME(0), datetimeoffset DATETIMEOFFSET(5), datetime \
         DATETIME2(2), char CHAR(7), varchar VARCHAR(5), varcharmax VARCHAR(MAX));"
     );
 }
+
+#[test]
+fn test_schema_overrides() {
+    let options = busser::SchemaOptions {
+        overrides: busser::overrides::Overrides::load(helper::get_test_file(
+            "simple_overrides.toml",
+        ))
+        .unwrap(),
+        ..Default::default()
+    };
+    let table = busser::csv_schema(
+        &helper::get_test_file("simple_test.csv"),
+        "test",
+        false,
+        &options,
+    )
+    .unwrap();
+    assert!(table.contains("Group_Num CHAR(3) NOT NULL, EmailAddress VARCHAR(100),"));
+}
+
+#[test]
+fn test_schema_override_too_short() {
+    let options = busser::SchemaOptions {
+        overrides: busser::overrides::Overrides::load(helper::get_test_file(
+            "simple_overrides_short.json",
+        ))
+        .unwrap(),
+        ..Default::default()
+    };
+    let err = busser::csv_schema(
+        &helper::get_test_file("simple_test.csv"),
+        "test",
+        false,
+        &options,
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("does not fit VARCHAR(20)"));
+}
+
+/// Account numbers such as ACME-000123, checked just before CHAR
+struct AccountNumber;
+
+impl TypeCheck for AccountNumber {
+    fn priority(&self) -> u32 {
+        1950
+    }
+
+    fn check(&self, value: &[u8], _subindex: usize) -> Option<SQLType> {
+        let digits = value.strip_prefix(b"ACME-")?;
+        (digits.len() == 6 && digits.iter().all(u8::is_ascii_digit)).then(|| SQLType {
+            name: SQLTypeName::Custom("dbo.AccountNumber"),
+            ..Default::default()
+        })
+    }
+}
+
+#[test]
+fn test_schema_custom_check() {
+    let mut options = busser::SchemaOptions::default();
+    options.checks.insert(Box::new(AccountNumber));
+    let table = busser::csv_schema(
+        &helper::get_test_file("custom_test.csv"),
+        "test",
+        false,
+        &options,
+    )
+    .unwrap();
+    assert_eq!(
+        table,
+        "DROP TABLE IF EXISTS test;\nCREATE TABLE test (id TINYINT, \
+        account dbo.AccountNumber, note VARCHAR(6));"
+    );
+}
+
+#[test]
+fn test_schema_override_compact_date() {
+    let options = busser::SchemaOptions {
+        overrides: busser::overrides::Overrides::load(helper::get_test_file("date_overrides.toml"))
+            .unwrap(),
+        ..Default::default()
+    };
+    let table = busser::csv_schema(
+        &helper::get_test_file("compact_dates_test.csv"),
+        "test",
+        false,
+        &options,
+    )
+    .unwrap();
+    assert_eq!(
+        table,
+        "DROP TABLE IF EXISTS test;\nCREATE TABLE test (id TINYINT, opened DATE);"
+    );
+}
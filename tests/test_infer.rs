@@ -1,4 +1,6 @@
-use busser::infer::{infer, SQLTypeName};
+use busser::infer::duration;
+use busser::infer::epoch::{detect, EpochUnit, IntegerRange};
+use busser::infer::{infer, infer_number, iso8601, SQLType, SQLTypeName};
 
 #[test]
 fn zero_is_bit() {
@@ -116,3 +118,196 @@ fn precision_count() {
     assert_eq!(0, infer(b"01:00:00.0", 0, 0).unwrap().size);
     assert_eq!(7, infer(b"01:00:00.012345678", 0, 0).unwrap().size);
 }
+
+#[test]
+fn month_name_is_date() {
+    assert_eq!(infer(b"Mar 4, 2023", 0, 0).unwrap().name, SQLTypeName::Date);
+}
+
+#[test]
+fn two_digit_year_is_date() {
+    assert_eq!(infer(b"4/3/23", 0, 0).unwrap().name, SQLTypeName::Date);
+}
+
+#[test]
+fn iso8601_date() {
+    let sqltype = infer(b"Mar 4, 2023", 0, 0).unwrap();
+    assert_eq!(iso8601(b"Mar 4, 2023", &sqltype).unwrap(), "2023-03-04");
+    let sqltype = infer(b"12/31/98", 0, 0).unwrap();
+    assert_eq!(iso8601(b"4/3/23", &sqltype).unwrap(), "2023-04-03");
+    assert_eq!(iso8601(b"12/31/98", &sqltype).unwrap(), "1998-12-31");
+}
+
+#[test]
+fn iso8601_datetime_period() {
+    let sqltype = infer(b"Jun 2, 2023 05:00 pm", 0, 0).unwrap();
+    assert_eq!(sqltype.name, SQLTypeName::Datetime2);
+    assert_eq!(
+        iso8601(b"Jun 2, 2023 05:00 pm", &sqltype).unwrap(),
+        "2023-06-02T17:00:00"
+    );
+}
+
+#[test]
+fn iso8601_leaves_other_types() {
+    let sqltype = infer(b"abcd", 0, 0).unwrap();
+    assert!(iso8601(b"abcd", &sqltype).is_none());
+}
+
+#[test]
+fn epoch_needs_range_or_hint() {
+    let sqltype = infer(b"1700000000", 0, 0).unwrap();
+    let mut range = IntegerRange::default();
+    range.update(b"1700000000");
+    assert_eq!(
+        detect("created", &sqltype, &range),
+        Some(EpochUnit::Seconds)
+    );
+    let mut range = IntegerRange::default();
+    range.update(b"1700000");
+    assert_eq!(detect("created", &sqltype, &range), None);
+    assert_eq!(
+        detect("created_ts", &sqltype, &range),
+        Some(EpochUnit::Seconds)
+    );
+}
+
+#[test]
+fn out_of_range_dates_are_text() {
+    assert_eq!(infer(b"0000-01-01", 0, 0).unwrap().name, SQLTypeName::Char);
+    assert_eq!(
+        infer(b"2023-01-01T00:00:00+15:00", 0, 0).unwrap().name,
+        SQLTypeName::Char
+    );
+    assert_eq!(
+        infer(b"2023-01-01T00:00:00+14:00", 0, 0).unwrap().name,
+        SQLTypeName::Datetimeoffset
+    );
+}
+
+#[test]
+fn unloadable_floats_are_text() {
+    for value in [&b"inf"[..], b"NaN", b"1e400", b"1e-320"] {
+        assert_eq!(infer(value, 0, 0).unwrap().name, SQLTypeName::Char);
+    }
+}
+
+#[test]
+fn numeric_precision_stays_within_38() {
+    let mut num = infer(b"12345678901234567890123456789012345", 0, 0).unwrap();
+    assert_eq!(num.name, SQLTypeName::Numeric);
+    num.merge(&infer(b"0.12345", 0, 0).unwrap());
+    assert_eq!(num.name, SQLTypeName::Float);
+    assert!(num.out_of_range);
+}
+
+#[test]
+fn leading_zeros_as_number() {
+    assert_eq!(infer(b"00125", 0, 0).unwrap().name, SQLTypeName::Char);
+    let num = infer_number(b"00125", 0, 0).unwrap();
+    assert_eq!(num.name, SQLTypeName::Tinyint);
+    assert_eq!(num.byte_length, 5);
+    assert_eq!(
+        infer_number(b"-007.50", 0, 0).unwrap().name,
+        SQLTypeName::Numeric
+    );
+    assert_eq!(infer_number(b"007A", 0, 0).unwrap().name, SQLTypeName::Char);
+}
+
+#[test]
+fn json_objects_and_arrays() {
+    assert_eq!(
+        infer(br#"{"a": [1, null]}"#, 0, 0).unwrap().name,
+        SQLTypeName::Json
+    );
+    assert_eq!(infer(b"[1, 2]", 0, 0).unwrap().name, SQLTypeName::Json);
+    assert_eq!(infer(b"{not json}", 0, 0).unwrap().name, SQLTypeName::Char);
+}
+
+#[test]
+fn well_formed_xml() {
+    let item = infer(br#"<item sku="A-1">Widget</item>"#, 0, 0).unwrap();
+    assert_eq!(item.name, SQLTypeName::Xml);
+    assert_eq!(infer(b"<a/><b/>", 0, 0).unwrap().name, SQLTypeName::Xml);
+    assert_eq!(infer(b"<a><b></a>", 0, 0).unwrap().name, SQLTypeName::Char);
+}
+
+#[test]
+fn hex_and_base64_are_binary() {
+    let prefixed = infer(b"0x48656C6C6F", 0, 0).unwrap();
+    assert_eq!((prefixed.name, prefixed.size), (SQLTypeName::Binary, 5));
+    let digest = infer(b"d41d8cd98f00b204e9800998ecf8427e", 0, 0).unwrap();
+    assert_eq!((digest.name, digest.size), (SQLTypeName::Binary, 16));
+    let encoded = infer(b"SGVsbG8sIFdvcmxkIQ==", 0, 0).unwrap();
+    assert_eq!((encoded.name, encoded.size), (SQLTypeName::Binary, 13));
+    assert_eq!(infer(b"0x123", 0, 0).unwrap().name, SQLTypeName::Char);
+    assert_eq!(infer(b"deadbeef", 0, 0).unwrap().name, SQLTypeName::Char);
+}
+
+#[test]
+fn alphanumeric_codes_are_not_base64() {
+    for code in [
+        &b"ABCD"[..],
+        b"test",
+        b"ProductCode12345",
+        b"abcdEFGH12345678",
+    ] {
+        assert_eq!(infer(code, 0, 0).unwrap().name, SQLTypeName::Char);
+    }
+    let slashed = infer(b"ab+/cdEFGH123456", 0, 0).unwrap();
+    assert_eq!((slashed.name, slashed.size), (SQLTypeName::Binary, 12));
+}
+
+#[test]
+fn wkt_is_geography_or_geometry() {
+    let name = |value: &[u8]| infer(value, 0, 0).unwrap().name;
+    assert_eq!(name(b"POINT(-74.006 40.7128)"), SQLTypeName::Geography);
+    assert_eq!(name(b"point z (1 2 3)"), SQLTypeName::Geography);
+    assert_eq!(name(b"MULTIPOINT(1 2, 3 4)"), SQLTypeName::Geography);
+    assert_eq!(
+        name(b"GEOMETRYCOLLECTION(POINT(1 2), LINESTRING(0 0, 1 1))"),
+        SQLTypeName::Geography
+    );
+    assert_eq!(name(b"LINESTRING EMPTY"), SQLTypeName::Geography);
+    assert_eq!(name(b"POINT(500000 4649776)"), SQLTypeName::Geometry);
+    assert_eq!(name(b"POINT(1)"), SQLTypeName::Char);
+    assert_eq!(name(b"POLYGON((0 0, 1 1)"), SQLTypeName::Char);
+    assert_eq!(name(b"CIRCLE(0 0, 1)"), SQLTypeName::Char);
+}
+
+#[test]
+fn durations_beyond_a_day() {
+    let name = |value: &[u8]| infer(value, 0, 0).unwrap().name;
+    assert_eq!(name(b"12:30:00"), SQLTypeName::Time);
+    assert_eq!(name(b"125:30:00"), SQLTypeName::Duration);
+    assert_eq!(name(b"3 days 04:00"), SQLTypeName::Duration);
+    assert_eq!(name(b"PT1H30M"), SQLTypeName::Duration);
+    assert_eq!(name(b"P1Y"), SQLTypeName::Char);
+    assert_eq!(name(b"PT"), SQLTypeName::Char);
+    let seconds = |value: &[u8]| duration::parse(value).map(|d| d.seconds);
+    assert_eq!(seconds(b"125:30:00"), Some(451_800));
+    assert_eq!(seconds(b"3 days 04:00"), Some(273_600));
+    assert_eq!(seconds(b"1 day"), Some(86_400));
+    assert_eq!(seconds(b"P1W2DT3H4M5S"), Some(788_645));
+    assert_eq!(seconds(b"PT1M30S"), Some(90));
+    assert_eq!(seconds(b"PT1S1M"), None);
+    assert_eq!(seconds(b"10:75"), None);
+}
+
+#[test]
+fn saved_types_reindex() {
+    let mut text = infer(b"hello", 0, 0).unwrap();
+    let number = infer(b"12", 0, 0).unwrap();
+    let saved = serde_json::to_string(&text).unwrap();
+    let mut loaded: SQLType = serde_json::from_str(&saved).unwrap();
+    loaded.reindex();
+    assert_eq!(loaded.index, text.index);
+    text.merge(&number);
+    loaded.merge(&number);
+    assert_eq!(loaded.name, text.name);
+    let custom = SQLType {
+        name: SQLTypeName::Custom("dbo.AccountNumber"),
+        ..Default::default()
+    };
+    assert!(serde_json::to_string(&custom).is_err());
+}